@@ -0,0 +1,195 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, DataEnum, Fields, Lit, Meta, NestedMeta, Type, Variant};
+
+/// Attributes (`#[desse(...)]`) applied on a struct or an enum
+#[derive(Default)]
+pub struct ContainerAttrs {
+    /// Layout version written in first byte of serialized object
+    pub version: Option<u8>,
+    /// Type used for deserializing bytes written with an earlier layout version
+    pub previous: Option<Type>,
+    /// Number of zero bytes reserved after fields (for forward compatibility)
+    pub reserved: usize,
+    /// Type used for storing tag of an enum (`u8`, `u16`, `u32` or `u64`)
+    pub tag_type: Option<String>,
+    /// Whether a CRC32C checksum of all the preceding bytes is appended to serialized object
+    pub checksum: bool,
+    /// Whether `desse::Field` constants (`FIELD_<NAME>`) are generated for fields of a struct
+    pub field_consts: bool,
+}
+
+impl ContainerAttrs {
+    /// Parses container attributes from attributes of a struct or an enum
+    pub fn from_attrs(attrs: &[Attribute]) -> Self {
+        let mut container_attrs = Self::default();
+
+        for meta in get_desse_metas(attrs) {
+            match meta {
+                Meta::NameValue(ref name_value) if name_value.ident == "version" => {
+                    let version = match name_value.lit {
+                        Lit::Int(ref lit) => lit.value(),
+                        _ => panic!("`version` should be an integer literal"),
+                    };
+
+                    if version > u64::from(u8::MAX) {
+                        panic!("`version` should fit in `u8`");
+                    }
+
+                    container_attrs.version = Some(version as u8);
+                }
+                Meta::NameValue(ref name_value) if name_value.ident == "previous" => {
+                    let previous = match name_value.lit {
+                        Lit::Str(ref lit) => lit
+                            .parse::<Type>()
+                            .expect("`previous` should be a valid type"),
+                        _ => panic!("`previous` should be a string literal"),
+                    };
+
+                    container_attrs.previous = Some(previous);
+                }
+                Meta::NameValue(ref name_value) if name_value.ident == "reserved" => {
+                    container_attrs.reserved = get_reserved(&name_value.lit);
+                }
+                Meta::NameValue(ref name_value) if name_value.ident == "tag_type" => {
+                    let tag_type = match name_value.lit {
+                        Lit::Str(ref lit) => lit.value(),
+                        _ => panic!("`tag_type` should be a string literal"),
+                    };
+
+                    match tag_type.as_str() {
+                        "u8" | "u16" | "u32" | "u64" => container_attrs.tag_type = Some(tag_type),
+                        _ => panic!("`tag_type` should be one of `u8`, `u16`, `u32` or `u64`"),
+                    }
+                }
+                Meta::Word(ref ident) if ident == "checksum" => container_attrs.checksum = true,
+                Meta::Word(ref ident) if ident == "field_consts" => {
+                    container_attrs.field_consts = true
+                }
+                _ => panic!("Unknown desse attribute on container"),
+            }
+        }
+
+        if container_attrs.previous.is_some() && container_attrs.version.is_none() {
+            panic!("`previous` can only be used along with `version`");
+        }
+
+        container_attrs
+    }
+
+    /// Calculates size expression for header (written before fields or variant) of serialized object
+    pub fn header_size_expr(&self) -> TokenStream {
+        match self.version {
+            None => quote! { 0 },
+            Some(_) => quote! { <u8>::SIZE },
+        }
+    }
+
+    /// Calculates size expression for trailer (written after fields or variant) of serialized object
+    pub fn trailer_size_expr(&self) -> TokenStream {
+        match self.checksum {
+            false => quote! { 0 },
+            true => quote! { <u32>::SIZE },
+        }
+    }
+}
+
+/// Attributes (`#[desse(...)]`) applied on an enum variant
+#[derive(Default)]
+pub struct VariantAttrs {
+    /// Number of zero bytes reserved after fields of variant (for forward compatibility)
+    pub reserved: usize,
+    /// Whether variant is a catch-all for unknown tags (with `tag` and raw `payload` fields)
+    pub other: bool,
+    /// Explicit tag of variant
+    pub tag: Option<u64>,
+}
+
+impl VariantAttrs {
+    /// Parses variant attributes from attributes of an enum variant
+    pub fn from_attrs(attrs: &[Attribute]) -> Self {
+        let mut variant_attrs = Self::default();
+
+        for meta in get_desse_metas(attrs) {
+            match meta {
+                Meta::NameValue(ref name_value) if name_value.ident == "reserved" => {
+                    variant_attrs.reserved = get_reserved(&name_value.lit);
+                }
+                Meta::Word(ref ident) if ident == "other" => variant_attrs.other = true,
+                Meta::NameValue(ref name_value) if name_value.ident == "tag" => {
+                    let tag = match name_value.lit {
+                        Lit::Int(ref lit) => lit.value(),
+                        _ => panic!("`tag` should be an integer literal"),
+                    };
+
+                    variant_attrs.tag = Some(tag);
+                }
+                _ => panic!("Unknown desse attribute on variant"),
+            }
+        }
+
+        if variant_attrs.other && variant_attrs.reserved != 0 {
+            panic!("`reserved` cannot be used on `other` variant");
+        }
+
+        variant_attrs
+    }
+}
+
+/// Returns catch-all (`#[desse(other)]`) variant of an enum (if any)
+pub fn get_other_variant(enum_data: &DataEnum) -> Option<&Variant> {
+    let mut other_variants = enum_data
+        .variants
+        .iter()
+        .filter(|variant| VariantAttrs::from_attrs(&variant.attrs).other);
+
+    let other_variant = other_variants.next()?;
+
+    if other_variants.next().is_some() {
+        panic!("Only one variant of an enum can be marked as `other`");
+    }
+
+    let field_names = match other_variant.fields {
+        Fields::Named(ref named_fields) => named_fields
+            .named
+            .iter()
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+
+    if field_names != ["tag", "payload"] {
+        panic!("`other` variant should have exactly two named fields: `tag` and `payload`");
+    }
+
+    Some(other_variant)
+}
+
+/// Returns number of reserved bytes from `reserved = N` literal
+fn get_reserved(lit: &Lit) -> usize {
+    match lit {
+        Lit::Int(ref lit) => lit.value() as usize,
+        _ => panic!("`reserved` should be an integer literal"),
+    }
+}
+
+/// Returns all the metas inside `#[desse(...)]` attributes
+fn get_desse_metas(attrs: &[Attribute]) -> Vec<Meta> {
+    let mut metas = Vec::new();
+
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("desse")) {
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => {
+                for nested in list.nested.into_iter() {
+                    match nested {
+                        NestedMeta::Meta(meta) => metas.push(meta),
+                        NestedMeta::Literal(_) => panic!("Invalid desse attribute"),
+                    }
+                }
+            }
+            _ => panic!("Invalid desse attribute"),
+        }
+    }
+
+    metas
+}
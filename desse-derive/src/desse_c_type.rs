@@ -0,0 +1,119 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Data::*;
+use syn::{DeriveInput, Fields};
+
+use crate::attrs::{ContainerAttrs, VariantAttrs};
+use crate::tags::EnumTags;
+
+/// Returns expressions for declaring field types and creating `CField`s (including `reserved` bytes)
+fn get_c_fields(fields: &Fields, reserved: usize) -> (Vec<TokenStream>, Vec<TokenStream>) {
+    let mut declarations = Vec::with_capacity(fields.iter().len());
+    let mut c_fields = Vec::with_capacity(fields.iter().len());
+
+    for (i, field) in fields.iter().enumerate() {
+        let field_type = &field.ty;
+        let field_name = match &field.ident {
+            Some(ref ident) => ident.to_string().trim_start_matches("r#").to_string(),
+            None => format!("_{}", i),
+        };
+
+        declarations.push(quote! {
+            <#field_type as DesseCType>::c_declare(header);
+        });
+        c_fields.push(quote! {
+            desse::CField::new::<#field_type>(#field_name)
+        });
+    }
+
+    if reserved != 0 {
+        c_fields.push(quote! {
+            desse::CField::reserved(#reserved)
+        });
+    }
+
+    (declarations, c_fields)
+}
+
+/// Returns `DesseCType` trait implementation
+pub fn get_desse_c_type_impl(input: DeriveInput) -> TokenStream {
+    let name = input.ident;
+    let attrs = ContainerAttrs::from_attrs(&input.attrs);
+    let header_fields = match attrs.version {
+        None => Vec::new(),
+        Some(_) => vec![quote! { desse::CField::new::<u8>("version") }],
+    };
+    let trailer_fields = match attrs.checksum {
+        false => Vec::new(),
+        true => vec![quote! { desse::CField::new::<u32>("checksum") }],
+    };
+
+    let declare = match &input.data {
+        Struct(ref struct_data) => {
+            let (declarations, c_fields) = get_c_fields(&struct_data.fields, attrs.reserved);
+
+            quote! {
+                #(#declarations)*
+                header.define_struct(Self::C_TYPE, <Self as DesseSized>::SIZE, &[#(#header_fields,)* #(#c_fields,)* #(#trailer_fields),*]);
+            }
+        }
+        Enum(ref enum_data) => {
+            let tags = EnumTags::new(&attrs, enum_data);
+            let size_type = &tags.tag_type;
+            let mut declarations = Vec::new();
+            let mut variants = Vec::with_capacity(enum_data.variants.len());
+
+            for (i, variant) in enum_data.variants.iter().enumerate() {
+                let variant_attrs = VariantAttrs::from_attrs(&variant.attrs);
+
+                if variant_attrs.other {
+                    continue;
+                }
+
+                let variant_name = variant
+                    .ident
+                    .to_string()
+                    .trim_start_matches("r#")
+                    .to_string();
+                let tag = tags.tag_value(i);
+                let (variant_declarations, c_fields) =
+                    get_c_fields(&variant.fields, variant_attrs.reserved);
+
+                declarations.extend(variant_declarations);
+                variants.push(quote! {
+                    desse::CVariant::new(#variant_name, #tag, &[#(#c_fields),*])
+                });
+            }
+
+            quote! {
+                #(#declarations)*
+                header.define_enum(
+                    Self::C_TYPE,
+                    <Self as DesseSized>::SIZE,
+                    &[#(#header_fields),*],
+                    desse::CField::new::<#size_type>("tag"),
+                    &[#(#variants),*],
+                    &[#(#trailer_fields),*],
+                );
+            }
+        }
+        Union(_) => panic!("This macro cannot be used on unions!"),
+    };
+
+    let c_type = name.to_string().trim_start_matches("r#").to_string();
+
+    quote! {
+        #[automatically_derived]
+        #[allow(unused_qualifications)]
+        #[allow(unused)]
+        impl DesseCType for #name {
+            const C_TYPE: &'static str = #c_type;
+
+            fn c_declare(header: &mut desse::CHeader) {
+                if header.declare::<Self>() {
+                    #declare
+                }
+            }
+        }
+    }
+}
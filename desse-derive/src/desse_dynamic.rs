@@ -0,0 +1,499 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::Data::*;
+use syn::{DeriveInput, Fields, GenericParam, Ident, Lifetime};
+
+use crate::attrs::{ContainerAttrs, VariantAttrs};
+use crate::tags::EnumTags;
+
+/// Names of `DesseDynamic` and `DesseOwned` functions used for serializing and deserializing fields (checked or
+/// unchecked)
+struct DynamicFns {
+    serialize: Ident,
+    deserialize: Ident,
+}
+
+impl DynamicFns {
+    fn new(unchecked: bool) -> Self {
+        let suffix = if unchecked { "_unchecked" } else { "" };
+
+        Self {
+            serialize: Ident::new(&format!("serialize_into{}", suffix), Span::call_site()),
+            deserialize: Ident::new(&format!("deserialize_owned{}", suffix), Span::call_site()),
+        }
+    }
+}
+
+/// Returns names of variables bound to fields (`__field0`, `__field1`, etc.)
+fn get_bindings(fields: &Fields) -> Vec<Ident> {
+    (0..fields.iter().len())
+        .map(|i| Ident::new(&format!("__field{}", i), Span::call_site()))
+        .collect()
+}
+
+/// Returns names of fields (as used in error context)
+fn get_field_names(fields: &Fields) -> Vec<String> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ref ident) => ident.to_string().trim_start_matches("r#").to_string(),
+            None => i.to_string(),
+        })
+        .collect()
+}
+
+/// Returns pattern (binding fields by reference) or constructor of `path` with fields bound to `bindings`
+fn get_pattern(
+    path: &TokenStream,
+    fields: &Fields,
+    bindings: &[Ident],
+    by_ref: bool,
+) -> TokenStream {
+    let bindings = bindings.iter().map(|binding| {
+        if by_ref {
+            quote! { ref #binding }
+        } else {
+            quote! { #binding }
+        }
+    });
+
+    match fields {
+        Fields::Unit => quote! { #path },
+        Fields::Named(named_fields) => {
+            let idents = named_fields.named.iter().map(|field| &field.ident);
+            quote! { #path { #(#idents: #bindings),* } }
+        }
+        Fields::Unnamed(_) => quote! { #path ( #(#bindings),* ) },
+    }
+}
+
+/// Returns expression for context of `variant` (if any) of enum `parent`
+fn get_variant_context(parent: &str, variant: Option<&Ident>) -> TokenStream {
+    match variant {
+        None => quote! {},
+        Some(variant) => {
+            let variant = variant.to_string();
+            quote! { .with_variant(#parent, #variant) }
+        }
+    }
+}
+
+/// Returns statements serializing fields bound to `bindings` into `writer`
+fn get_serialize_fields(bindings: &[Ident], fns: &DynamicFns) -> Vec<TokenStream> {
+    let serialize_fn = &fns.serialize;
+
+    bindings
+        .iter()
+        .map(|binding| quote! { desse::DesseDynamic::#serialize_fn(#binding, desse::Writer::inner_mut(&mut writer))?; })
+        .collect()
+}
+
+/// Returns statements deserializing fields into `bindings` from a generic reader. `header` is the size of bytes
+/// before first field (used for computing offset of a failed field).
+fn get_deserialize_fields(
+    parent: &str,
+    variant: Option<&Ident>,
+    header: &TokenStream,
+    fields: &Fields,
+    bindings: &[Ident],
+    fns: &DynamicFns,
+) -> Vec<TokenStream> {
+    let deserialize_fn = &fns.deserialize;
+    let variant_context = get_variant_context(parent, variant);
+    let field_names = get_field_names(fields);
+
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let field_type = &field.ty;
+            let binding = &bindings[i];
+            let field_name = &field_names[i];
+            let previous = &bindings[..i];
+
+            quote! {
+                let #binding = match <#field_type as desse::DesseOwned>::#deserialize_fn(desse::Reader::inner_mut(&mut reader)) {
+                    Ok(value) => value,
+                    Err(err) => return Err(err
+                        .with_type_name(core::any::type_name::<#field_type>())
+                        .with_field(#parent, #field_name)
+                        #variant_context
+                        .with_offset(#header #(+ desse::DesseDynamic::serialized_size(&#previous))*)),
+                };
+            }
+        })
+        .collect()
+}
+
+/// Returns `true` if `tokens` (of a field type) contain `lifetime`
+fn contains_lifetime(tokens: TokenStream, lifetime: &Lifetime) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Group(ref group) if contains_lifetime(group.stream(), lifetime) => {
+                return true
+            }
+            TokenTree::Punct(ref punct) if punct.as_char() == '\'' => {
+                if let Some(TokenTree::Ident(ident)) = tokens.peek() {
+                    if *ident == lifetime.ident {
+                        return true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    false
+}
+
+/// Returns statements deserializing fields into `bindings` by borrowing from `reader` (a
+/// `&mut LimitedReader<&'de [u8]>`). Fields whose type does not contain lifetime of derived type are deserialized as
+/// owned values (using `DesseDynamic`), so that they only need to implement `DesseDynamic`.
+fn get_borrowed_fields(
+    parent: &str,
+    variant: Option<&Ident>,
+    lifetime: &Lifetime,
+    fields: &Fields,
+    bindings: &[Ident],
+) -> Vec<TokenStream> {
+    let variant_context = get_variant_context(parent, variant);
+    let field_names = get_field_names(fields);
+
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let field_type = &field.ty;
+            let binding = &bindings[i];
+            let field_name = &field_names[i];
+
+            let deserialize = if contains_lifetime(quote! { #field_type }, lifetime) {
+                quote! { <#field_type as desse::DesseBorrowed<#lifetime>>::deserialize_borrowed_from(reader) }
+            } else {
+                quote! { <#field_type as desse::DesseOwned>::deserialize_owned(&mut *reader) }
+            };
+
+            quote! {
+                let __offset = reader.bytes_read() - __start;
+                let #binding = match #deserialize {
+                    Ok(value) => value,
+                    Err(err) => return Err(err
+                        .with_type_name(core::any::type_name::<#field_type>())
+                        .with_field(#parent, #field_name)
+                        #variant_context
+                        .with_offset(__offset)),
+                };
+            }
+        })
+        .collect()
+}
+
+/// Returns lifetime parameter of derived type (if any). Panics if type has any other generic parameter.
+fn get_lifetime(input: &DeriveInput) -> Option<Lifetime> {
+    let mut lifetime = None;
+
+    for param in input.generics.params.iter() {
+        match param {
+            GenericParam::Lifetime(ref lifetime_def) if lifetime.is_none() => {
+                lifetime = Some(lifetime_def.lifetime.clone())
+            }
+            GenericParam::Lifetime(_) => {
+                panic!("`DesseDynamic` can only be derived for types with at most one lifetime parameter")
+            }
+            _ => panic!("`DesseDynamic` cannot be derived for types with generic type parameters"),
+        }
+    }
+
+    lifetime
+}
+
+/// Panics if any container attribute not supported by dynamic encoding is used
+fn check_container_attrs(attrs: &ContainerAttrs) {
+    if attrs.version.is_some() || attrs.checksum || attrs.reserved != 0 {
+        panic!("`version`, `checksum` and `reserved` are not supported by `DesseDynamic`");
+    }
+}
+
+/// Returns `DesseDynamic`, `DesseOwned` (for types without a lifetime parameter) and `DesseBorrowed` trait
+/// implementations
+pub fn get_desse_dynamic_impl(input: DeriveInput) -> TokenStream {
+    let name = &input.ident;
+    let parent = name.to_string();
+    let attrs = ContainerAttrs::from_attrs(&input.attrs);
+    check_container_attrs(&attrs);
+
+    let lifetime = get_lifetime(&input);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let borrowed_lifetime = lifetime
+        .clone()
+        .unwrap_or_else(|| Lifetime::new("'de", Span::call_site()));
+    let borrowed_impl_generics = match lifetime {
+        None => quote! { <'de> },
+        Some(_) => quote! { #impl_generics },
+    };
+
+    let checked = DynamicFns::new(false);
+    let unchecked = DynamicFns::new(true);
+
+    let (
+        min_size,
+        size,
+        serialize,
+        serialize_unchecked,
+        deserialize,
+        deserialize_unchecked,
+        borrowed,
+    ) = match &input.data {
+        Struct(ref struct_data) => {
+            let fields = &struct_data.fields;
+            let bindings = get_bindings(fields);
+            let path = quote! { #name };
+            let pattern = get_pattern(&path, fields, &bindings, true);
+            let constructor = get_pattern(&path, fields, &bindings, false);
+            let field_types = fields.iter().map(|field| &field.ty);
+            let header = quote! { 0 };
+            let size_bindings = &bindings;
+
+            let serialize_fields = |fns: &DynamicFns| {
+                let serialize_stmts = get_serialize_fields(&bindings, fns);
+
+                quote! {
+                    let #pattern = *self;
+                    #(#serialize_stmts)*
+                    Ok(())
+                }
+            };
+            let deserialize_fields = |fns: &DynamicFns| {
+                let stmts = get_deserialize_fields(&parent, None, &header, fields, &bindings, fns);
+
+                quote! {
+                    #(#stmts)*
+                    Ok(#constructor)
+                }
+            };
+            let borrowed_stmts =
+                get_borrowed_fields(&parent, None, &borrowed_lifetime, fields, &bindings);
+
+            (
+                quote! { 0 #(+ <#field_types as desse::DesseDynamic>::MIN_SERIALIZED_SIZE)* },
+                quote! {
+                    let #pattern = *self;
+                    0 #(+ desse::DesseDynamic::serialized_size(#size_bindings))*
+                },
+                serialize_fields(&checked),
+                serialize_fields(&unchecked),
+                deserialize_fields(&checked),
+                deserialize_fields(&unchecked),
+                quote! {
+                    #(#borrowed_stmts)*
+                    Ok(#constructor)
+                },
+            )
+        }
+        Enum(ref enum_data) => {
+            let tags = EnumTags::new(&attrs, enum_data);
+            let tag_type = &tags.tag_type;
+            let header = quote! { <#tag_type as desse::DesseSized>::SIZE };
+            let invalid_tag = quote! {
+                _ => Err(desse::Error::from(desse::ErrorKind::InvalidTag)
+                    .with_type_name(core::any::type_name::<Self>())
+                    .with_offset(0))
+            };
+
+            let mut size_arms = Vec::with_capacity(enum_data.variants.len());
+            let mut serialize_arms = Vec::with_capacity(enum_data.variants.len());
+            let mut serialize_unchecked_arms = Vec::with_capacity(enum_data.variants.len());
+            let mut deserialize_arms = Vec::with_capacity(enum_data.variants.len());
+            let mut deserialize_unchecked_arms = Vec::with_capacity(enum_data.variants.len());
+            let mut borrowed_arms = Vec::with_capacity(enum_data.variants.len());
+
+            for (i, variant) in enum_data.variants.iter().enumerate() {
+                let variant_attrs = VariantAttrs::from_attrs(&variant.attrs);
+
+                if variant_attrs.other || variant_attrs.reserved != 0 {
+                    panic!("`other` and `reserved` variants are not supported by `DesseDynamic`");
+                }
+
+                let variant_name = &variant.ident;
+                let fields = &variant.fields;
+                let bindings = get_bindings(fields);
+                let path = quote! { #name::#variant_name };
+                let pattern = get_pattern(&path, fields, &bindings, true);
+                let constructor = get_pattern(&path, fields, &bindings, false);
+                let tag = tags.tag(i);
+
+                let size_bindings = &bindings;
+                size_arms.push(quote! {
+                    #pattern => #header #(+ desse::DesseDynamic::serialized_size(#size_bindings))*
+                });
+
+                for (arms, fns) in [
+                    (&mut serialize_arms, &checked),
+                    (&mut serialize_unchecked_arms, &unchecked),
+                ] {
+                    let serialize_fn = &fns.serialize;
+                    let serialize_stmts = get_serialize_fields(&bindings, fns);
+
+                    arms.push(quote! {
+                        #pattern => {
+                            <#tag_type as desse::DesseDynamic>::#serialize_fn(&#tag, &mut writer)?;
+                            #(#serialize_stmts)*
+                        }
+                    });
+                }
+
+                for (arms, fns) in [
+                    (&mut deserialize_arms, &checked),
+                    (&mut deserialize_unchecked_arms, &unchecked),
+                ] {
+                    let stmts = get_deserialize_fields(
+                        &parent,
+                        Some(variant_name),
+                        &header,
+                        fields,
+                        &bindings,
+                        fns,
+                    );
+
+                    arms.push(quote! {
+                        #tag => {
+                            #(#stmts)*
+                            Ok(#constructor)
+                        }
+                    });
+                }
+
+                let borrowed_stmts = get_borrowed_fields(
+                    &parent,
+                    Some(variant_name),
+                    &borrowed_lifetime,
+                    fields,
+                    &bindings,
+                );
+
+                borrowed_arms.push(quote! {
+                    #tag => {
+                        #(#borrowed_stmts)*
+                        Ok(#constructor)
+                    }
+                });
+            }
+
+            let serialize_fields = |arms: &[TokenStream]| {
+                quote! {
+                    match *self {
+                        #(#arms),*
+                    }
+                    Ok(())
+                }
+            };
+            let deserialize_fields = |arms: &[TokenStream], fns: &DynamicFns| {
+                let deserialize_fn = &fns.deserialize;
+
+                quote! {
+                    match <#tag_type as desse::DesseOwned>::#deserialize_fn(&mut reader)? {
+                        #(#arms,)*
+                        #invalid_tag
+                    }
+                }
+            };
+
+            (
+                header.clone(),
+                quote! {
+                    match *self {
+                        #(#size_arms),*
+                    }
+                },
+                serialize_fields(&serialize_arms),
+                serialize_fields(&serialize_unchecked_arms),
+                deserialize_fields(&deserialize_arms, &checked),
+                deserialize_fields(&deserialize_unchecked_arms, &unchecked),
+                quote! {
+                    match <#tag_type as desse::DesseOwned>::deserialize_owned(&mut *reader)? {
+                        #(#borrowed_arms,)*
+                        #invalid_tag
+                    }
+                },
+            )
+        }
+        Union(_) => panic!("This macro cannot be used on unions!"),
+    };
+
+    let dynamic_impl = quote! {
+        #[automatically_derived]
+        #[allow(unused_qualifications)]
+        #[allow(unused)]
+        impl #impl_generics desse::DesseDynamic for #name #ty_generics #where_clause {
+            type Output = Self;
+
+            const MIN_SERIALIZED_SIZE: usize = #min_size;
+
+            #[inline]
+            fn serialized_size(&self) -> usize {
+                #size
+            }
+
+            #[inline]
+            fn serialize(&self) -> desse::Result<desse::__private::Vec<u8>> {
+                let mut bytes = desse::__private::Vec::with_capacity(desse::DesseDynamic::serialized_size(self));
+                desse::DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+                Ok(bytes)
+            }
+
+            #[inline]
+            fn serialize_into<W: desse::Writer>(&self, mut writer: W) -> desse::Result<()> {
+                #serialize
+            }
+
+            #[inline]
+            fn serialize_into_unchecked<W: desse::Writer>(&self, mut writer: W) -> desse::Result<()> {
+                #serialize_unchecked
+            }
+        }
+    };
+
+    // Types borrowing from input cannot be deserialized into an owned value (only `DesseBorrowed` is implemented)
+    let owned_impl = match lifetime {
+        Some(_) => quote! {},
+        None => quote! {
+            #[automatically_derived]
+            #[allow(unused_qualifications)]
+            #[allow(unused)]
+            impl desse::DesseOwned for #name {
+                #[inline]
+                fn deserialize_owned<R: desse::Reader>(mut reader: R) -> desse::Result<Self::Output> {
+                    #deserialize
+                }
+
+                #[inline]
+                fn deserialize_owned_unchecked<R: desse::Reader>(mut reader: R) -> desse::Result<Self::Output> {
+                    #deserialize_unchecked
+                }
+            }
+        },
+    };
+
+    let borrowed_impl = quote! {
+        #[automatically_derived]
+        #[allow(unused_qualifications)]
+        #[allow(unused)]
+        impl #borrowed_impl_generics desse::DesseBorrowed<#borrowed_lifetime> for #name #ty_generics #where_clause {
+            #[inline]
+            fn deserialize_borrowed_from(reader: &mut desse::LimitedReader<&#borrowed_lifetime [u8]>) -> desse::Result<Self> {
+                let __start = reader.bytes_read();
+                #borrowed
+            }
+        }
+    };
+
+    quote! {
+        #dynamic_impl
+        #owned_impl
+        #borrowed_impl
+    }
+}
@@ -1,17 +1,69 @@
-use proc_macro2::TokenStream;
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::Data::*;
-use syn::DeriveInput;
+use syn::{DeriveInput, Fields, Ident};
 
+use crate::attrs::{get_other_variant, ContainerAttrs};
 use crate::expr::SizeExpr;
+use crate::tags::EnumTags;
 
 /// Returns `DesseSized` trait implementation
 pub fn get_desse_sized_impl(input: DeriveInput) -> TokenStream {
     let name = input.ident;
+    let attrs = ContainerAttrs::from_attrs(&input.attrs);
+    let header = attrs.header_size_expr();
+    let trailer = attrs.trailer_size_expr();
+
+    let mut other_payload_size = quote! {};
+    let mut field_consts = quote! {};
 
     let expr = match &input.data {
-        Struct(ref struct_data) => SizeExpr::for_struct(struct_data),
-        Enum(ref enum_data) => SizeExpr::for_enum(enum_data),
+        Struct(ref struct_data) => {
+            let fields_size_expr = SizeExpr::for_struct(struct_data);
+            let reserved = attrs.reserved;
+
+            if attrs.field_consts {
+                let consts = get_field_consts(&struct_data.fields, &header);
+
+                field_consts = quote! {
+                    #[automatically_derived]
+                    #[allow(unused_qualifications)]
+                    #[allow(unused)]
+                    impl #name {
+                        #(#consts)*
+                    }
+                };
+            }
+
+            quote! { #fields_size_expr + #reserved }
+        }
+        Enum(ref enum_data) => {
+            if attrs.reserved != 0 {
+                panic!("`reserved` should be used on variants of an enum");
+            }
+
+            if attrs.field_consts {
+                panic!("`field_consts` can only be used on structs");
+            }
+
+            if get_other_variant(enum_data).is_some() {
+                let payload_size_expr = SizeExpr::get_variant_max_size_expr(&enum_data.variants);
+
+                other_payload_size = quote! {
+                    #[automatically_derived]
+                    #[allow(unused_qualifications)]
+                    #[allow(unused)]
+                    impl #name {
+                        /// Size of `payload` of catch-all (`#[desse(other)]`) variant
+                        pub const OTHER_PAYLOAD_SIZE: usize = #payload_size_expr;
+                    }
+                };
+            }
+
+            SizeExpr::for_enum(enum_data, &EnumTags::new(&attrs, enum_data).tag_type)
+        }
         Union(_) => panic!("This macro cannot be used on unions!"),
     };
 
@@ -20,7 +72,46 @@ pub fn get_desse_sized_impl(input: DeriveInput) -> TokenStream {
         #[allow(unused_qualifications)]
         #[allow(unused)]
         impl DesseSized for #name {
-            const SIZE: usize = #expr;
+            const SIZE: usize = #header + #expr + #trailer;
         }
+
+        #other_payload_size
+        #field_consts
     }
 }
+
+/// Returns `desse::Field` constants (`FIELD_<NAME>`, with same visibility as field) for fields of a struct
+fn get_field_consts(fields: &Fields, header: &TokenStream) -> Vec<TokenStream> {
+    let mut offset = header.clone();
+    let mut consts = Vec::new();
+    let mut const_names = HashSet::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let field_name = match field.ident {
+            Some(ref ident) => ident.to_string().trim_start_matches("r#").to_string(),
+            None => i.to_string(),
+        };
+        let const_name = format!("FIELD_{}", field_name.to_uppercase());
+
+        if !const_names.insert(const_name.clone()) {
+            panic!(
+                "Field `{}` has same constant name (`{}`) as another field",
+                field_name, const_name
+            );
+        }
+
+        let const_name = Ident::new(&const_name, Span::call_site());
+        let doc = format!("Location of field `{}` in serialized bytes", field_name);
+        let vis = &field.vis;
+        let ty = &field.ty;
+
+        consts.push(quote! {
+            #[doc = #doc]
+            #vis const #const_name: desse::Field<Self, #ty> = desse::Field::new(#offset);
+        });
+
+        offset = quote! { #offset + <#ty>::SIZE };
+    }
+
+    consts
+}
@@ -3,24 +3,121 @@ use quote::quote;
 use syn::Data::*;
 use syn::DeriveInput;
 
+use crate::attrs::ContainerAttrs;
 use crate::expr::{DeserializeExpr, SerializeExpr};
+use crate::tags::EnumTags;
 
 /// Returns `Desse` trait implementation
 pub fn get_desse_static_impl(input: DeriveInput) -> TokenStream {
     let name = input.ident;
+    let attrs = ContainerAttrs::from_attrs(&input.attrs);
+    let offset = attrs.header_size_expr();
 
-    let (serialize, deserialize) = match &input.data {
+    let (mut serialize, mut deserialize, mut deserialize_strict) = match &input.data {
         Struct(ref struct_data) => (
-            SerializeExpr::for_struct(&name, &struct_data),
-            DeserializeExpr::for_struct(&name, &struct_data),
-        ),
-        Enum(ref enum_data) => (
-            SerializeExpr::for_enum(&name, &enum_data),
-            DeserializeExpr::for_enum(&name, &enum_data),
+            SerializeExpr::for_struct(&name, struct_data, &offset, attrs.reserved),
+            DeserializeExpr::for_struct(&name, struct_data, &offset, attrs.reserved, false),
+            DeserializeExpr::for_struct(&name, struct_data, &offset, attrs.reserved, true),
         ),
+        Enum(ref enum_data) => {
+            let tags = EnumTags::new(&attrs, enum_data);
+
+            (
+                SerializeExpr::for_enum(&name, enum_data, &offset, &tags),
+                DeserializeExpr::for_enum(&name, enum_data, &offset, &tags, false),
+                DeserializeExpr::for_enum(&name, enum_data, &offset, &tags, true),
+            )
+        }
         Union(_) => panic!("This macro cannot be used on unions!"),
     };
 
+    if attrs.checksum {
+        let checksum_error = DeserializeExpr::get_error_expr(
+            quote! { ChecksumMismatch },
+            quote! { Self::SIZE - <u32>::SIZE },
+        );
+        serialize = quote! {
+            #serialize
+            let checksum = desse::crc32c(&bytes[..(Self::SIZE - <u32>::SIZE)]);
+            bytes[(Self::SIZE - <u32>::SIZE)..].copy_from_slice(&checksum.to_le_bytes());
+        };
+        let checksum_check = quote! {
+            let mut checksum = [0; <u32>::SIZE];
+            checksum.copy_from_slice(&bytes[(Self::SIZE - <u32>::SIZE)..]);
+
+            if desse::crc32c(&bytes[..(Self::SIZE - <u32>::SIZE)]) != u32::from_le_bytes(checksum) {
+                return Err(#checksum_error);
+            }
+        };
+
+        deserialize = quote! {
+            #checksum_check
+            #deserialize
+        };
+        deserialize_strict = quote! {
+            #checksum_check
+            #deserialize_strict
+        };
+    }
+
+    let mut versioned = quote! {};
+
+    if let Some(version) = attrs.version {
+        let previous = match attrs.previous {
+            None => {
+                let error_expr =
+                    DeserializeExpr::get_error_expr(quote! { UnknownVersion }, quote! { 0 });
+                quote! { Err(#error_expr) }
+            }
+            Some(ref previous) => quote! {
+                <#previous as desse::DesseVersioned>::deserialize_versioned(bytes).map(From::from)
+            },
+        };
+
+        serialize = quote! {
+            bytes[0] = #version;
+            #serialize
+        };
+        let version_check = quote! {
+            if bytes[0] != #version {
+                let bytes: &[u8] = &bytes[..];
+                return #previous;
+            }
+        };
+
+        deserialize = quote! {
+            #version_check
+            #deserialize
+        };
+        deserialize_strict = quote! {
+            #version_check
+            #deserialize_strict
+        };
+        versioned = quote! {
+            #[automatically_derived]
+            #[allow(unused_qualifications)]
+            #[allow(unused)]
+            impl desse::DesseVersioned for #name {
+                const VERSION: u8 = #version;
+
+                #[inline]
+                fn deserialize_versioned(bytes: &[u8]) -> desse::Result<Self> {
+                    match bytes.first() {
+                        None => Err(desse::ErrorKind::InvalidSliceLength.into()),
+                        Some(&#version) => {
+                            if bytes.len() < Self::SIZE {
+                                return Err(desse::ErrorKind::InvalidSliceLength.into());
+                            }
+
+                            unsafe { <Self as DesseStatic>::deserialize_from(&*(bytes.as_ptr() as *const [u8; Self::SIZE])) }
+                        }
+                        Some(_) => #previous,
+                    }
+                }
+            }
+        };
+    }
+
     quote! {
         #[automatically_derived]
         #[allow(unused_qualifications)]
@@ -44,6 +141,13 @@ pub fn get_desse_static_impl(input: DeriveInput) -> TokenStream {
             fn deserialize_from(bytes: &Self::Output) -> desse::Result<Self> {
                 #deserialize
             }
+
+            #[inline]
+            fn deserialize_from_strict(bytes: &Self::Output) -> desse::Result<Self> {
+                #deserialize_strict
+            }
         }
+
+        #versioned
     }
 }
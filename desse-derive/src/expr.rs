@@ -5,3 +5,36 @@ mod size_expr;
 pub use deserialize_expr::DeserializeExpr;
 pub use serialize_expr::SerializeExpr;
 pub use size_expr::SizeExpr;
+
+use syn::{GenericArgument, PathArguments, Type};
+
+/// Returns types of `Ok` and `Err` values if `ty` is a `Result<T, E>` (written with two type arguments, e.g.,
+/// `Result<T, E>` or `core::result::Result<T, E>`). Fields of such types are serialized by `DesseStatic` derive macro
+/// itself (as `DesseStatic` cannot be implemented for `Result<T, E>` on stable Rust).
+pub fn get_result_types(ty: &Type) -> Option<(&Type, &Type)> {
+    let path = match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => &type_path.path,
+        _ => return None,
+    };
+
+    let segment = path.segments.last()?.into_value();
+
+    if segment.ident != "Result" {
+        return None;
+    }
+
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => &args.args,
+        _ => return None,
+    };
+
+    let mut types = args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+
+    match (types.next(), types.next(), types.next()) {
+        (Some(ok_type), Some(err_type), None) if args.len() == 2 => Some((ok_type, err_type)),
+        _ => None,
+    }
+}
@@ -1,18 +1,174 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{DataEnum, DataStruct, Fields, Ident, Index};
+use syn::{DataEnum, DataStruct, Fields, Ident, Type};
 
-use crate::expr::SizeExpr;
+use crate::attrs::{get_other_variant, VariantAttrs};
+use crate::expr::{get_result_types, SizeExpr};
+use crate::tags::EnumTags;
 
 /// Helper struct for computing deserialize expression for different types
 pub struct DeserializeExpr;
 
 impl DeserializeExpr {
+    /// Returns expression for an error of given kind raised by derived type itself at `offset`
+    pub fn get_error_expr<O: ToTokens>(kind: TokenStream, offset: O) -> TokenStream {
+        quote! {
+            desse::Error::from(desse::ErrorKind::#kind)
+                .with_type_name(core::any::type_name::<Self>())
+                .with_offset(#offset)
+        }
+    }
+
+    /// Returns expression for deserializing a field of type `field_type` located at `counter`, adding field's context
+    /// to error (if any)
+    fn get_field_expr<C: ToTokens>(
+        parent: &Ident,
+        variant: Option<&Ident>,
+        field_name: &str,
+        field_type: &Type,
+        counter: C,
+        deserialize_fn: &Ident,
+    ) -> TokenStream {
+        let parent = parent.to_string();
+        let variant_context = match variant {
+            None => quote! {},
+            Some(variant) => {
+                let variant = variant.to_string();
+                quote! { .with_variant(#parent, #variant) }
+            }
+        };
+
+        let (ok_type, err_type) = match get_result_types(field_type) {
+            None => {
+                return quote! {
+                    match <#field_type as DesseStatic>::#deserialize_fn(&*(bytes[ (#counter) .. ( #counter + <#field_type>::SIZE ) ].as_ptr() as *const [u8; <#field_type>::SIZE])) {
+                        Ok(value) => value,
+                        Err(err) => return Err(err
+                            .with_type_name(core::any::type_name::<#field_type>())
+                            .with_field(#parent, #field_name)
+                            #variant_context
+                            .with_offset(#counter)),
+                    }
+                };
+            }
+            Some(types) => types,
+        };
+
+        // A tag byte (`0` for `Ok` and `1` for `Err`) followed by value, padded with zeros to size of larger value
+        // (padding is checked only in strict mode)
+        let strict = deserialize_fn == "deserialize_from_strict";
+        let error_context = quote! {
+            .with_type_name(core::any::type_name::<#field_type>())
+            .with_field(#parent, #field_name)
+            #variant_context
+        };
+
+        let variant_expr = |value_type: &Type| {
+            let start = quote! { #counter + 1 + <#value_type>::SIZE };
+            let padding_expr = if strict {
+                quote! {
+                    if let Some(position) = bytes[(#start)..(#counter + <#field_type>::SIZE)].iter().position(|byte| *byte != 0) {
+                        return Err(desse::Error::from(desse::ErrorKind::NonZeroPadding)
+                            #error_context
+                            .with_offset(#start + position));
+                    }
+                }
+            } else {
+                quote! {}
+            };
+
+            quote! {
+                #padding_expr
+
+                match <#value_type as DesseStatic>::#deserialize_fn(&*(bytes[(#counter + 1)..(#start)].as_ptr() as *const [u8; <#value_type>::SIZE])) {
+                    Ok(value) => value,
+                    Err(err) => return Err(err
+                        #error_context
+                        .with_offset(#counter + 1)),
+                }
+            }
+        };
+
+        let ok_expr = variant_expr(ok_type);
+        let err_expr = variant_expr(err_type);
+
+        quote! {
+            match bytes[#counter] {
+                0 => core::result::Result::Ok({ #ok_expr }),
+                1 => core::result::Result::Err({ #err_expr }),
+                _ => return Err(desse::Error::from(desse::ErrorKind::InvalidTag)
+                    #error_context
+                    .with_offset(#counter)),
+            }
+        }
+    }
+
+    /// Returns name of `DesseStatic` function used for deserializing fields
+    fn get_deserialize_fn(strict: bool) -> Ident {
+        if strict {
+            Ident::new("deserialize_from_strict", Span::call_site())
+        } else {
+            Ident::new("deserialize_from", Span::call_site())
+        }
+    }
+
+    /// Calculates expression for checking `reserved` bytes placed after fields (only in strict mode)
+    fn get_reserved_expr<C: ToTokens>(
+        init_counter: C,
+        fields: &Fields,
+        reserved: usize,
+        strict: bool,
+    ) -> TokenStream {
+        if reserved == 0 || !strict {
+            return quote! {};
+        }
+
+        let fields_size_expr = SizeExpr::get_size_expr_for_fields(fields);
+        let error_expr = Self::get_error_expr(
+            quote! { NonZeroReserved },
+            quote! { #init_counter + #fields_size_expr },
+        );
+
+        quote! {
+            if bytes[(#init_counter + #fields_size_expr)..(#init_counter + #fields_size_expr + #reserved)].iter().any(|byte| *byte != 0) {
+                return Err(#error_expr);
+            }
+        }
+    }
+
+    /// Calculates expression for checking padding bytes placed after fields and `reserved` bytes of a variant up to
+    /// `end` (only in strict mode)
+    fn get_padding_expr<C: ToTokens, E: ToTokens>(
+        init_counter: C,
+        end: E,
+        fields: &Fields,
+        reserved: usize,
+        strict: bool,
+    ) -> TokenStream {
+        if !strict {
+            return quote! {};
+        }
+
+        let fields_size_expr = SizeExpr::get_size_expr_for_fields(fields);
+        let start = quote! { #init_counter + #fields_size_expr + #reserved };
+        let error_expr =
+            Self::get_error_expr(quote! { NonZeroPadding }, quote! { #start + position });
+
+        quote! {
+            if let Some(position) = bytes[(#start)..(#end)].iter().position(|byte| *byte != 0) {
+                return Err(#error_expr);
+            }
+        }
+    }
+
     /// Calculates serialize expression for fields
     fn get_deserialize_expr_for_fields<T: ToTokens, C: ToTokens>(
         container_name: T,
+        parent: &Ident,
+        variant: Option<&Ident>,
         init_counter: C,
         fields: &Fields,
+        deserialize_fn: &Ident,
     ) -> TokenStream {
         match fields {
             Fields::Unit => quote! { Ok(#container_name) },
@@ -24,14 +180,22 @@ impl DeserializeExpr {
                     let mut counter = quote! { #init_counter };
 
                     for field in named_fields.named.iter() {
-                        let field_name = match &field.ident {
+                        let ident = match &field.ident {
                             None => unreachable!(),
-                            Some(ref ident) => quote! { #ident },
+                            Some(ref ident) => ident,
                         };
                         let field_type = &field.ty;
+                        let field_expr = Self::get_field_expr(
+                            parent,
+                            variant,
+                            ident.to_string().trim_start_matches("r#"),
+                            field_type,
+                            &counter,
+                            deserialize_fn,
+                        );
 
                         exprs.push(quote! {
-                            #field_name: <#field_type as DesseStatic>::deserialize_from(&*(bytes[ (#counter) .. ( #counter + <#field_type>::SIZE ) ].as_ptr() as *const [u8; <#field_type>::SIZE]))?
+                            #ident: #field_expr
                         });
 
                         counter = quote! { #counter + <#field_type>::SIZE };
@@ -53,12 +217,17 @@ impl DeserializeExpr {
                     let mut exprs = Vec::with_capacity(unnamed_fields.unnamed.len());
                     let mut counter = quote! { #init_counter };
 
-                    for field in unnamed_fields.unnamed.iter() {
+                    for (i, field) in unnamed_fields.unnamed.iter().enumerate() {
                         let field_type = &field.ty;
 
-                        exprs.push(quote! {
-                            <#field_type as DesseStatic>::deserialize_from(&*(bytes[ (#counter) .. ( #counter + <#field_type>::SIZE ) ].as_ptr() as *const [u8; <#field_type>::SIZE]))?
-                        });
+                        exprs.push(Self::get_field_expr(
+                            parent,
+                            variant,
+                            &i.to_string(),
+                            field_type,
+                            &counter,
+                            deserialize_fn,
+                        ));
 
                         counter = quote! { #counter + <#field_type>::SIZE };
                     }
@@ -74,37 +243,109 @@ impl DeserializeExpr {
     }
 
     /// Calculates size expression for [`DataStruct`](syn::DataStruct)
-    pub fn for_struct(name: &Ident, struct_data: &DataStruct) -> TokenStream {
-        Self::get_deserialize_expr_for_fields(name, quote! { 0 }, &struct_data.fields)
+    pub fn for_struct(
+        name: &Ident,
+        struct_data: &DataStruct,
+        offset: &TokenStream,
+        reserved: usize,
+        strict: bool,
+    ) -> TokenStream {
+        let deserialize_fn = Self::get_deserialize_fn(strict);
+        let reserved_expr = Self::get_reserved_expr(offset, &struct_data.fields, reserved, strict);
+        let fields_expr = Self::get_deserialize_expr_for_fields(
+            name,
+            name,
+            None,
+            offset,
+            &struct_data.fields,
+            &deserialize_fn,
+        );
+
+        quote! {
+            #reserved_expr
+            #fields_expr
+        }
     }
 
     /// Calculates size expression for [`DataEnum`](syn::DataEnum)
-    pub fn for_enum(name: &Ident, enum_data: &DataEnum) -> TokenStream {
+    pub fn for_enum(
+        name: &Ident,
+        enum_data: &DataEnum,
+        offset: &TokenStream,
+        tags: &EnumTags,
+        strict: bool,
+    ) -> TokenStream {
         let variant_count = enum_data.variants.len();
+        let deserialize_fn = Self::get_deserialize_fn(strict);
 
-        let size_type = SizeExpr::get_variant_count_size_type(variant_count);
+        let size_type = &tags.tag_type;
         let mut match_exprs = Vec::with_capacity(variant_count);
 
         let variant_expr = quote! {
-            let variant = unsafe { <#size_type as DesseStatic>::deserialize_from(&*(bytes[0..<#size_type>::SIZE].as_ptr() as *const [u8; <#size_type>::SIZE]))? };
+            let variant = unsafe { <#size_type as DesseStatic>::deserialize_from(&*(bytes[(#offset)..(#offset + <#size_type>::SIZE)].as_ptr() as *const [u8; <#size_type>::SIZE]))? };
         };
 
+        let max_size_expr = SizeExpr::get_variant_max_size_expr(&enum_data.variants);
+        let variants_end = quote! { #offset + <#size_type>::SIZE + #max_size_expr };
+
         for (i, variant) in enum_data.variants.iter().enumerate() {
+            if VariantAttrs::from_attrs(&variant.attrs).other {
+                continue;
+            }
+
             let variant_name = &variant.ident;
             let variant_expr = Self::get_deserialize_expr_for_fields(
                 quote! { #name:: #variant_name},
-                quote! { <#size_type>::SIZE },
+                name,
+                Some(variant_name),
+                quote! { #offset + <#size_type>::SIZE },
+                &variant.fields,
+                &deserialize_fn,
+            );
+            let variant_reserved_expr = Self::get_reserved_expr(
+                quote! { #offset + <#size_type>::SIZE },
                 &variant.fields,
+                VariantAttrs::from_attrs(&variant.attrs).reserved,
+                strict,
             );
-            let index = Index::from(i);
+            let variant_padding_expr = Self::get_padding_expr(
+                quote! { #offset + <#size_type>::SIZE },
+                &variants_end,
+                &variant.fields,
+                VariantAttrs::from_attrs(&variant.attrs).reserved,
+                strict,
+            );
+            let index = tags.tag(i);
             match_exprs.push(quote! {
-                #index => #variant_expr
+                #index => {
+                    #variant_reserved_expr
+                    #variant_padding_expr
+                    #variant_expr
+                }
             });
         }
 
-        match_exprs.push(quote! {
-            _ => unreachable!()
-        });
+        match get_other_variant(enum_data) {
+            None => {
+                let error_expr = Self::get_error_expr(quote! { InvalidTag }, offset);
+
+                match_exprs.push(quote! {
+                    _ => Err(#error_expr)
+                });
+            }
+            Some(other_variant) => {
+                let variant_name = &other_variant.ident;
+
+                match_exprs.push(quote! {
+                    _ => unsafe {
+                        Ok(#name:: #variant_name {
+                            tag: variant,
+                            payload: *(bytes[(#offset + <#size_type>::SIZE)..(#offset + <#size_type>::SIZE + #name::OTHER_PAYLOAD_SIZE)].as_ptr() as *const [u8; #name::OTHER_PAYLOAD_SIZE]),
+                        })
+                    }
+                });
+            }
+        }
 
         let match_expr = quote! {
             match variant {
@@ -3,14 +3,54 @@ use std::str::FromStr;
 
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use syn::{DataEnum, DataStruct, Fields, Ident, Index};
+use syn::{DataEnum, DataStruct, Fields, Ident, Type};
 
-use crate::expr::SizeExpr;
+use crate::attrs::VariantAttrs;
+use crate::expr::{get_result_types, SizeExpr};
+use crate::tags::EnumTags;
 
 /// Helper struct for computing serialize expression for different types
 pub struct SerializeExpr;
 
 impl SerializeExpr {
+    /// Returns expression for serializing field `field_ref` of type `field_type` at `counter`
+    fn get_field_expr<C: ToTokens>(
+        field_ref: &TokenStream,
+        field_type: &Type,
+        counter: C,
+    ) -> TokenStream {
+        let (ok_type, err_type) = match get_result_types(field_type) {
+            None => {
+                return quote! {
+                    DesseStatic::serialize_into(#field_ref, &mut *(bytes[(#counter)..(#counter + <#field_type>::SIZE)].as_mut_ptr() as *mut [u8; <#field_type>::SIZE]));
+                };
+            }
+            Some(types) => types,
+        };
+
+        // A tag byte (`0` for `Ok` and `1` for `Err`) followed by value, padded with zeros to size of larger value
+        let variant_expr = |tag: u8, value_type: &Type| {
+            quote! {
+                bytes[#counter] = #tag;
+                DesseStatic::serialize_into(value, &mut *(bytes[(#counter + 1)..(#counter + 1 + <#value_type>::SIZE)].as_mut_ptr() as *mut [u8; <#value_type>::SIZE]));
+
+                for byte in bytes[(#counter + 1 + <#value_type>::SIZE)..(#counter + <#field_type>::SIZE)].iter_mut() {
+                    *byte = 0;
+                }
+            }
+        };
+
+        let ok_expr = variant_expr(0, ok_type);
+        let err_expr = variant_expr(1, err_type);
+
+        quote! {
+            match #field_ref {
+                core::result::Result::Ok(value) => { #ok_expr }
+                core::result::Result::Err(value) => { #err_expr }
+            }
+        }
+    }
+
     /// Calculates serialize expression for fields
     fn get_serialize_expr_for_fields<T: ToTokens + Display, C: ToTokens>(
         container_prefix: T,
@@ -34,9 +74,7 @@ impl SerializeExpr {
                         TokenStream::from_str(&format!("{}{}", container_prefix, field_name))
                             .unwrap();
 
-                    exprs.push(quote! {
-                        DesseStatic::serialize_into(#field_ref, &mut *(bytes[(#counter)..(#counter + <#field_type>::SIZE)].as_mut_ptr() as *mut [u8; <#field_type>::SIZE]));
-                    });
+                    exprs.push(Self::get_field_expr(&field_ref, field_type, &counter));
 
                     counter = quote! { #counter + <#field_type>::SIZE };
                 }
@@ -57,9 +95,7 @@ impl SerializeExpr {
                     let field_ref =
                         TokenStream::from_str(&format!("{}{}", container_prefix, i)).unwrap();
 
-                    exprs.push(quote! {
-                        DesseStatic::serialize_into(#field_ref, &mut *(bytes[(#counter)..(#counter + <#field_type>::SIZE)].as_mut_ptr() as *mut [u8; <#field_type>::SIZE]));
-                    });
+                    exprs.push(Self::get_field_expr(&field_ref, field_type, &counter));
 
                     counter = quote! { #counter + <#field_type>::SIZE };
                 }
@@ -73,20 +109,90 @@ impl SerializeExpr {
         }
     }
 
+    /// Calculates expression for zeroing `reserved` bytes placed after fields
+    fn get_reserved_expr<C: ToTokens>(
+        init_counter: C,
+        fields: &Fields,
+        reserved: usize,
+    ) -> TokenStream {
+        if reserved == 0 {
+            return quote! {};
+        }
+
+        let fields_size_expr = SizeExpr::get_size_expr_for_fields(fields);
+
+        quote! {
+            for byte in bytes[(#init_counter + #fields_size_expr)..(#init_counter + #fields_size_expr + #reserved)].iter_mut() {
+                *byte = 0;
+            }
+        }
+    }
+
+    /// Calculates expression for zeroing padding bytes placed after fields and `reserved` bytes of a variant up to `end`
+    fn get_padding_expr<C: ToTokens, E: ToTokens>(
+        init_counter: C,
+        end: E,
+        fields: &Fields,
+        reserved: usize,
+    ) -> TokenStream {
+        let fields_size_expr = SizeExpr::get_size_expr_for_fields(fields);
+
+        quote! {
+            for byte in bytes[(#init_counter + #fields_size_expr + #reserved)..(#end)].iter_mut() {
+                *byte = 0;
+            }
+        }
+    }
+
     /// Calculates  expression for [`DataStruct`](syn::DataStruct)
-    pub fn for_struct(_: &Ident, struct_data: &DataStruct) -> TokenStream {
-        Self::get_serialize_expr_for_fields(quote! { &self. }, quote! { 0 }, &struct_data.fields)
+    pub fn for_struct(
+        _: &Ident,
+        struct_data: &DataStruct,
+        offset: &TokenStream,
+        reserved: usize,
+    ) -> TokenStream {
+        let fields_expr =
+            Self::get_serialize_expr_for_fields(quote! { &self. }, offset, &struct_data.fields);
+        let reserved_expr = Self::get_reserved_expr(offset, &struct_data.fields, reserved);
+
+        quote! {
+            #fields_expr
+            #reserved_expr
+        }
     }
 
     /// Calculates serialize expression for [`DataEnum`](syn::DataEnum)
-    pub fn for_enum(name: &Ident, enum_data: &DataEnum) -> TokenStream {
+    pub fn for_enum(
+        name: &Ident,
+        enum_data: &DataEnum,
+        offset: &TokenStream,
+        tags: &EnumTags,
+    ) -> TokenStream {
         let variant_count = enum_data.variants.len();
 
-        let size_type = SizeExpr::get_variant_count_size_type(variant_count);
+        let size_type = &tags.tag_type;
         let mut match_exprs = Vec::with_capacity(variant_count);
 
+        let max_size_expr = SizeExpr::get_variant_max_size_expr(&enum_data.variants);
+        let variants_end = quote! { #offset + <#size_type>::SIZE + #max_size_expr };
+
         for (i, variant) in enum_data.variants.iter().enumerate() {
-            let index = Index::from(i);
+            let index = tags.tag(i);
+
+            if VariantAttrs::from_attrs(&variant.attrs).other {
+                let variant_name = &variant.ident;
+
+                match_exprs.push(quote! {
+                    #name:: #variant_name { ref tag, ref payload } => {
+                        unsafe {
+                            DesseStatic::serialize_into(tag, &mut *(bytes[(#offset)..(#offset + <#size_type>::SIZE)].as_mut_ptr() as *mut [u8; <#size_type>::SIZE]));
+                        }
+                        bytes[(#offset + <#size_type>::SIZE)..(#offset + <#size_type>::SIZE + #name::OTHER_PAYLOAD_SIZE)].copy_from_slice(&payload[..]);
+                    }
+                });
+
+                continue;
+            }
 
             let field_prefix = match variant.fields {
                 Fields::Unit => quote! {},
@@ -127,18 +233,32 @@ impl SerializeExpr {
             let variant_name = &variant.ident;
             let variant_init_expr = quote! {
                 unsafe {
-                    DesseStatic::serialize_into(&(#index as #size_type), &mut *(bytes[0..<#size_type>::SIZE].as_mut_ptr() as *mut [u8; <#size_type>::SIZE]));
+                    DesseStatic::serialize_into(&(#index as #size_type), &mut *(bytes[(#offset)..(#offset + <#size_type>::SIZE)].as_mut_ptr() as *mut [u8; <#size_type>::SIZE]));
                 }
             };
             let variant_impl_expr = Self::get_serialize_expr_for_fields(
                 field_prefix,
-                quote! { <#size_type>::SIZE },
+                quote! { #offset + <#size_type>::SIZE },
+                &variant.fields,
+            );
+            let variant_reserved_expr = Self::get_reserved_expr(
+                quote! { #offset + <#size_type>::SIZE },
+                &variant.fields,
+                VariantAttrs::from_attrs(&variant.attrs).reserved,
+            );
+
+            let variant_padding_expr = Self::get_padding_expr(
+                quote! { #offset + <#size_type>::SIZE },
+                &variants_end,
                 &variant.fields,
+                VariantAttrs::from_attrs(&variant.attrs).reserved,
             );
 
             let variant_expr = quote! {
                 #variant_init_expr
                 #variant_impl_expr
+                #variant_reserved_expr
+                #variant_padding_expr
             };
 
             match_exprs.push(quote! {
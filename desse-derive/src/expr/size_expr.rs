@@ -3,6 +3,8 @@ use quote::quote;
 use syn::punctuated::Punctuated;
 use syn::{DataEnum, DataStruct, Field, Fields, Type, Variant};
 
+use crate::attrs::VariantAttrs;
+
 /// Helper struct for computing size expression for different types
 pub struct SizeExpr;
 
@@ -10,7 +12,7 @@ impl SizeExpr {
     /// Calculates size expression for punctuated fields
     fn get_size_expr_for_punctuated_field<T>(fields: &Punctuated<Field, T>) -> TokenStream {
         if fields.is_empty() {
-            return quote! { 0 };
+            quote! { 0 }
         } else {
             let types = fields.iter().map(|field| &field.ty).collect::<Vec<&Type>>();
             quote! { #(<#types>::SIZE)+* }
@@ -18,7 +20,7 @@ impl SizeExpr {
     }
 
     /// Calculates size expression for fields
-    fn get_size_expr_for_fields(fields: &Fields) -> TokenStream {
+    pub fn get_size_expr_for_fields(fields: &Fields) -> TokenStream {
         match fields {
             Fields::Unit => quote! { 0 },
             Fields::Named(named_fields) => {
@@ -31,31 +33,36 @@ impl SizeExpr {
     }
 
     /// Calculates size expression for punctuated variants
-    fn get_size_expr_for_punctuated_variant<T>(variants: &Punctuated<Variant, T>) -> TokenStream {
+    fn get_size_expr_for_punctuated_variant<T>(
+        variants: &Punctuated<Variant, T>,
+        tag_type: &TokenStream,
+    ) -> TokenStream {
         if variants.is_empty() {
-            return quote! { 0 };
+            quote! { 0 }
         } else {
-            let count_size_expr = Self::get_variant_count_size_expr(variants.len());
             let max_size_expr = Self::get_variant_max_size_expr(variants);
 
-            quote! { #count_size_expr + #max_size_expr }
+            quote! { <#tag_type>::SIZE + #max_size_expr }
         }
     }
 
-    /// Calculates size expression for variant
-    #[allow(unused)]
+    /// Calculates size expression for variant (including reserved bytes). Size of `other` variant is zero as its
+    /// payload overlaps with other variants.
     fn get_size_expr_for_variant(variant: &Variant) -> TokenStream {
-        Self::get_size_expr_for_fields(&variant.fields)
-    }
+        let attrs = VariantAttrs::from_attrs(&variant.attrs);
+
+        if attrs.other {
+            return quote! { 0 };
+        }
+
+        let fields_size_expr = Self::get_size_expr_for_fields(&variant.fields);
+        let reserved = attrs.reserved;
 
-    /// Calculates size expression for number of variants (used for enums)
-    fn get_variant_count_size_expr(len: usize) -> TokenStream {
-        let size_type = Self::get_variant_count_size_type(len);
-        quote! { <#size_type>::SIZE }
+        quote! { #fields_size_expr + #reserved }
     }
 
     /// Calculates size expression for maximum sized variant
-    fn get_variant_max_size_expr<T>(variants: &Punctuated<Variant, T>) -> TokenStream {
+    pub fn get_variant_max_size_expr<T>(variants: &Punctuated<Variant, T>) -> TokenStream {
         let mut max_size_expr = quote! { 0 };
 
         for variant in variants {
@@ -66,15 +73,15 @@ impl SizeExpr {
         max_size_expr
     }
 
-    /// Calculates size type for number of variants (used for enums)
+    /// Calculates size type for number of variants (used for enums without explicit `tag_type`)
     pub fn get_variant_count_size_type(len: usize) -> TokenStream {
-        if len <= <u8>::max_value() as usize {
+        if len <= u8::MAX as usize {
             quote! { u8 }
-        } else if len <= <u16>::max_value() as usize {
+        } else if len <= u16::MAX as usize {
             quote! { u16 }
-        } else if len <= <u32>::max_value() as usize {
+        } else if len <= u32::MAX as usize {
             quote! { u32 }
-        } else if len <= <u64>::max_value() as usize {
+        } else if len <= u64::MAX as usize {
             quote! { u64 }
         } else {
             quote! { u128 }
@@ -87,7 +94,7 @@ impl SizeExpr {
     }
 
     /// Calculates size expression for [`DataEnum`](syn::DataEnum)
-    pub fn for_enum(enum_data: &DataEnum) -> TokenStream {
-        Self::get_size_expr_for_punctuated_variant(&enum_data.variants)
+    pub fn for_enum(enum_data: &DataEnum, tag_type: &TokenStream) -> TokenStream {
+        Self::get_size_expr_for_punctuated_variant(&enum_data.variants, tag_type)
     }
 }
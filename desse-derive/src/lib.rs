@@ -1,20 +1,38 @@
+#![recursion_limit = "256"]
+
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
+mod attrs;
+mod desse_c_type;
+mod desse_dynamic;
 mod desse_sized;
 mod desse_static;
 mod expr;
+mod tags;
 
-#[proc_macro_derive(DesseSized)]
+#[proc_macro_derive(DesseSized, attributes(desse))]
 pub fn desse_sized_macro_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     desse_sized::get_desse_sized_impl(input).into()
 }
 
-#[proc_macro_derive(DesseStatic)]
+#[proc_macro_derive(DesseStatic, attributes(desse))]
 pub fn desse_macro_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     desse_static::get_desse_static_impl(input).into()
 }
+
+#[proc_macro_derive(DesseCType, attributes(desse))]
+pub fn desse_c_type_macro_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    desse_c_type::get_desse_c_type_impl(input).into()
+}
+
+#[proc_macro_derive(DesseDynamic, attributes(desse))]
+pub fn desse_dynamic_macro_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    desse_dynamic::get_desse_dynamic_impl(input).into()
+}
@@ -0,0 +1,85 @@
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::ToTokens;
+use syn::{DataEnum, Ident};
+
+use crate::attrs::{ContainerAttrs, VariantAttrs};
+use crate::expr::SizeExpr;
+
+/// Tags (written before fields of a variant) of all the variants of an enum
+pub struct EnumTags {
+    /// Type used for storing tag
+    pub tag_type: TokenStream,
+    /// Tags of variants (in order of declaration)
+    tags: Vec<u64>,
+}
+
+impl EnumTags {
+    /// Computes tag type and tags of variants of an enum. Panics if any tag does not fit in tag type or if two variants
+    /// have same tag.
+    pub fn new(attrs: &ContainerAttrs, enum_data: &DataEnum) -> Self {
+        let (tag_type, max_tag) = match attrs.tag_type.as_deref() {
+            None => {
+                let tag_type = SizeExpr::get_variant_count_size_type(enum_data.variants.len());
+                let max_tag = Self::get_max_tag(&tag_type.to_string());
+
+                (tag_type, max_tag)
+            }
+            Some(tag_type) => (
+                Ident::new(tag_type, Span::call_site()).into_token_stream(),
+                Self::get_max_tag(tag_type),
+            ),
+        };
+
+        if enum_data.variants.len() as u128 > u128::from(max_tag) + 1 {
+            panic!("Number of variants does not fit in `{}` tag", tag_type);
+        }
+
+        let mut tags: Vec<u64> = Vec::with_capacity(enum_data.variants.len());
+        let mut next_tag = 0u128;
+
+        for variant in enum_data.variants.iter() {
+            let tag = match VariantAttrs::from_attrs(&variant.attrs).tag {
+                Some(tag) => u128::from(tag),
+                None => next_tag,
+            };
+
+            if tag > u128::from(max_tag) {
+                panic!(
+                    "Tag of variant `{}` does not fit in `{}` tag",
+                    variant.ident, tag_type
+                );
+            }
+
+            let tag = tag as u64;
+
+            if tags.contains(&tag) {
+                panic!("Tag of variant `{}` is already in use", variant.ident);
+            }
+
+            tags.push(tag);
+            next_tag = u128::from(tag) + 1;
+        }
+
+        Self { tag_type, tags }
+    }
+
+    /// Returns unsuffixed literal of tag of variant at given index
+    pub fn tag(&self, index: usize) -> Literal {
+        Literal::u64_unsuffixed(self.tags[index])
+    }
+
+    /// Returns tag of variant at given index
+    pub fn tag_value(&self, index: usize) -> u64 {
+        self.tags[index]
+    }
+
+    /// Returns maximum value of given tag type
+    fn get_max_tag(tag_type: &str) -> u64 {
+        match tag_type {
+            "u8" => u64::from(u8::MAX),
+            "u16" => u64::from(u16::MAX),
+            "u32" => u64::from(u32::MAX),
+            _ => u64::MAX,
+        }
+    }
+}
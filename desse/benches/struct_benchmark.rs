@@ -1,7 +1,7 @@
 #[macro_use]
 extern crate serde_derive;
 
-use criterion::{black_box, criterion_group, criterion_main, Benchmark, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 use bincode::{deserialize, serialize_into};
 use desse::{DesseDynamic, DesseSized, DesseStatic};
@@ -34,65 +34,62 @@ enum MySerdeEnum {
 
 #[allow(unused_must_use)]
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench(
-        "struct::serialize",
-        Benchmark::new("desse::serialize", |b| {
-            b.iter(|| {
-                let my_struct: MyDesseStruct = MyDesseStruct {
-                    a: 253,
-                    b: 64016,
-                    c: MyDesseEnum::Variant2(64016),
-                };
-                black_box(DesseStatic::serialize(black_box(&my_struct)));
-            })
+    let mut group = c.benchmark_group("struct::serialize");
+    group.bench_function("desse::serialize", |b| {
+        b.iter(|| {
+            let my_struct: MyDesseStruct = MyDesseStruct {
+                a: 253,
+                b: 64016,
+                c: MyDesseEnum::Variant2(64016),
+            };
+            black_box(DesseStatic::serialize(black_box(&my_struct)));
         })
-        .with_function("bincode::serialize", |b| {
-            let mut buffer = Vec::new();
-            b.iter(|| {
-                buffer.clear();
-                let my_struct: MySerdeStruct = MySerdeStruct {
-                    a: 253,
-                    b: 64016,
-                    c: MySerdeEnum::Variant2(64016),
-                };
-                black_box(serialize_into(&mut buffer, black_box(&my_struct)));
-            })
-        }),
-    );
+    });
+    group.bench_function("bincode::serialize", |b| {
+        let mut buffer = Vec::new();
+        b.iter(|| {
+            buffer.clear();
+            let my_struct: MySerdeStruct = MySerdeStruct {
+                a: 253,
+                b: 64016,
+                c: MySerdeEnum::Variant2(64016),
+            };
+            black_box(serialize_into(&mut buffer, black_box(&my_struct)));
+        })
+    });
+    group.finish();
 
-    c.bench(
-        "struct::deserialize",
-        Benchmark::new("desse::deserialize", |b| {
-            b.iter(|| {
-                let bytes: [u8; 6] = [253, 16, 250, 1, 16, 250];
-                black_box(MyDesseStruct::deserialize_from(black_box(&bytes)));
-            })
+    let mut group = c.benchmark_group("struct::deserialize");
+    group.bench_function("desse::deserialize", |b| {
+        b.iter(|| {
+            let bytes: [u8; 6] = [253, 16, 250, 1, 16, 250];
+            black_box(MyDesseStruct::deserialize_from(black_box(&bytes)));
+        })
+    });
+    group.bench_function("bincode::deserialize", |b| {
+        b.iter(|| {
+            let bytes: [u8; 9] = [253, 16, 250, 1, 0, 0, 0, 16, 250];
+            black_box(deserialize::<MySerdeStruct>(black_box(&bytes)));
         })
-        .with_function("bincode::deserialize", |b| {
-            b.iter(|| {
-                let bytes: [u8; 9] = [253, 16, 250, 1, 0, 0, 0, 16, 250];
-                black_box(deserialize::<MySerdeStruct>(black_box(&bytes)));
-            })
-        }),
-    );
+    });
+    group.finish();
 
-    c.bench(
-        "dynamic::serde",
-        Benchmark::new("desse:serde", |b| {
-            let v = vec!["hello".to_string(), "world".to_string()];
-            b.iter(|| {
-                let serialized = DesseDynamic::serialize(&v).unwrap();
-                <Vec<String>>::deserialize_from(&*serialized).unwrap();
-            })
+    let mut group = c.benchmark_group("dynamic::serde");
+    group.bench_function("desse:serde", |b| {
+        let v = vec!["hello".to_string(), "world".to_string()];
+        b.iter(|| {
+            let serialized = DesseDynamic::serialize(&v).unwrap();
+            <Vec<String>>::deserialize_from(&*serialized).unwrap();
+        })
+    });
+    group.bench_function("bincode::serde", |b| {
+        let v = vec!["hello".to_string(), "world".to_string()];
+        b.iter(|| {
+            let serialized = bincode::serialize(&v).unwrap();
+            bincode::deserialize::<Vec<String>>(&serialized).unwrap();
         })
-        .with_function("bincode::serde", |b| {
-            let v = vec!["hello".to_string(), "world".to_string()];
-            b.iter(|| {
-                let serialized = bincode::serialize(&v).unwrap();
-                bincode::deserialize::<Vec<String>>(&serialized).unwrap();
-            })
-        }),
-    );
+    });
+    group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -23,16 +23,16 @@ impl DesseStatic for MyEnum {
     #[inline]
     fn serialize_into(&self, bytes: &mut Self::Output) {
         match self {
-            MyEnum::Variant1 => (&mut bytes[0..1]).copy_from_slice(&DesseStatic::serialize(&0u8)),
+            MyEnum::Variant1 => bytes[0..1].copy_from_slice(&DesseStatic::serialize(&0u8)),
             MyEnum::Variant2(ref a, ref b) => {
-                (&mut bytes[0..1]).copy_from_slice(&DesseStatic::serialize(&1u8));
-                (&mut bytes[1..2]).copy_from_slice(&DesseStatic::serialize(a));
-                (&mut bytes[2..4]).copy_from_slice(&DesseStatic::serialize(b));
+                bytes[0..1].copy_from_slice(&DesseStatic::serialize(&1u8));
+                bytes[1..2].copy_from_slice(&DesseStatic::serialize(a));
+                bytes[2..4].copy_from_slice(&DesseStatic::serialize(b));
             }
             MyEnum::Variant3 { ref a, ref b } => {
-                (&mut bytes[0..1]).copy_from_slice(&DesseStatic::serialize(&2u8));
-                (&mut bytes[1..5]).copy_from_slice(&DesseStatic::serialize(a));
-                (&mut bytes[5..9]).copy_from_slice(&DesseStatic::serialize(b));
+                bytes[0..1].copy_from_slice(&DesseStatic::serialize(&2u8));
+                bytes[1..5].copy_from_slice(&DesseStatic::serialize(a));
+                bytes[5..9].copy_from_slice(&DesseStatic::serialize(b));
             }
         }
     }
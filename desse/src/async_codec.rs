@@ -0,0 +1,580 @@
+#![cfg(feature = "async")]
+//! Async framed codec for reading and writing [`DesseDynamic`] values from
+//! [`AsyncRead`] and to [`AsyncWrite`] streams.
+//!
+//! Each value is written as a frame: length of serialized value (encoded as `u64`) followed by the serialized value.
+//! Frames use same encoding as [`FrameWriter`](crate::FrameWriter) (without checksum), and payload of a frame cannot be
+//! longer than [`DEFAULT_MAX_FRAME_SIZE`] unless configured otherwise.
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::future::poll_fn;
+use std::io;
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+use crate::{DesseDynamic, DesseOwned, DesseSized, ErrorKind, Result, DEFAULT_MAX_FRAME_SIZE};
+
+/// Size of length prefix of a frame
+const LEN_SIZE: usize = <u64>::SIZE;
+
+/// Number of bytes read from underlying reader in one go
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Writes `value` as a frame in `writer` and flushes it.
+///
+/// # Error
+///
+/// This function returns error (`ErrorKind::FrameTooLarge`) when serialized size of `value` is greater than
+/// [`DEFAULT_MAX_FRAME_SIZE`]. Nothing is written in this case.
+pub async fn write_value<T, W>(writer: &mut W, value: &T) -> Result<()>
+where
+    T: DesseDynamic,
+    W: AsyncWrite + Unpin,
+{
+    if value.serialized_size() > DEFAULT_MAX_FRAME_SIZE {
+        return Err(ErrorKind::FrameTooLarge.into());
+    }
+
+    let len = value.serialized_size() as u64;
+
+    let mut bytes = Vec::with_capacity(LEN_SIZE + value.serialized_size());
+    DesseDynamic::serialize_into(&len, &mut bytes)?;
+    value.serialize_into(&mut bytes)?;
+
+    write_all(writer, &bytes).await?;
+    poll_fn(|cx| Pin::new(&mut *writer).poll_flush(cx)).await?;
+
+    Ok(())
+}
+
+/// Reads exactly one frame from `reader` and deserializes its value.
+///
+/// This function never reads bytes beyond the end of frame. But, it is not cancellation safe, i.e., if the returned
+/// future is dropped before completion, bytes of a partially read frame are lost. Use
+/// [`Decoder`] when cancellation safety is needed.
+///
+/// # Error
+///
+/// This function returns error (`ErrorKind::FrameTooLarge`) when length of frame is greater than
+/// [`DEFAULT_MAX_FRAME_SIZE`] (payload of frame is not consumed in this case) and
+/// error (`ErrorKind::TrailingBytes`) when value does not consume complete payload of frame.
+pub async fn read_value<T, R>(reader: &mut R) -> Result<T::Output>
+where
+    T: DesseOwned,
+    R: AsyncRead + Unpin,
+{
+    let mut len_bytes = [0; LEN_SIZE];
+    read_exact(reader, &mut len_bytes).await?;
+
+    let len = get_len(&len_bytes, DEFAULT_MAX_FRAME_SIZE)?;
+
+    // Buffer grows as bytes arrive so that a corrupted length does not result in a huge allocation
+    let mut payload = Vec::new();
+
+    while payload.len() < len {
+        let start = payload.len();
+        payload.resize(core::cmp::min(len, start + CHUNK_SIZE), 0);
+        read_exact(reader, &mut payload[start..]).await?;
+    }
+
+    T::deserialize_exact(&payload)
+}
+
+/// Stateful decoder which buffers partial input until a complete frame is available
+///
+/// All the bytes read from underlying reader are kept in the decoder. So, a future returned by
+/// [`read()`](self::Decoder::read) can be dropped without losing any data.
+#[derive(Debug)]
+pub struct Decoder<T: DesseOwned> {
+    buf: Vec<u8>,
+    /// Offset of first byte of `buf` which is not decoded yet
+    start: usize,
+    max_frame_size: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: DesseOwned> Default for Decoder<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DesseOwned> Decoder<T> {
+    /// Creates a new decoder with maximum frame size of [`DEFAULT_MAX_FRAME_SIZE`]
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_max_frame_size(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Creates a new decoder with given maximum frame size
+    #[inline]
+    pub fn with_max_frame_size(max_frame_size: usize) -> Self {
+        Self {
+            buf: Vec::new(),
+            start: 0,
+            max_frame_size,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns maximum size of payload of a frame
+    #[inline]
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Returns number of buffered bytes
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    /// Appends `bytes` to buffered input
+    #[inline]
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Decodes next value if a complete frame is buffered. Returns `Ok(None)` if more input is needed.
+    ///
+    /// # Error
+    ///
+    /// When deserialization of a value fails (including when value does not consume complete payload of its frame, with
+    /// `ErrorKind::TrailingBytes`), its frame is discarded and error is returned. Subsequent frames can still be decoded.
+    ///
+    /// This function returns error (`ErrorKind::FrameTooLarge`) when length of frame is greater than maximum frame
+    /// size. The frame is not discarded in this case, so, input after it cannot be decoded.
+    pub fn decode(&mut self) -> Result<Option<T::Output>> {
+        let buffered = &self.buf[self.start..];
+
+        if buffered.len() < LEN_SIZE {
+            return Ok(None);
+        }
+
+        let len = get_len(&buffered[..LEN_SIZE], self.max_frame_size)?;
+
+        if buffered.len() - LEN_SIZE < len {
+            return Ok(None);
+        }
+
+        let value = T::deserialize_exact(&buffered[LEN_SIZE..(LEN_SIZE + len)]);
+        self.consume(LEN_SIZE + len);
+
+        value.map(Some)
+    }
+
+    /// Marks `len` buffered bytes as decoded. Decoded bytes are removed from buffer only once they make up at least
+    /// half of it, so that each byte is moved a constant number of times (on average).
+    #[inline]
+    fn consume(&mut self, len: usize) {
+        self.start += len;
+
+        if self.start == self.buf.len() {
+            self.buf.clear();
+            self.start = 0;
+        } else if self.start >= CHUNK_SIZE && self.start * 2 >= self.buf.len() {
+            self.buf.drain(..self.start);
+            self.start = 0;
+        }
+    }
+
+    /// Attempts to read next value from `reader`. Returns `Ok(None)` when `reader` reaches end of input on a frame
+    /// boundary.
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::Io(UnexpectedEof)`) when `reader` reaches end of input in middle of a
+    /// frame.
+    pub fn poll_read<R>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut reader: Pin<&mut R>,
+    ) -> Poll<Result<Option<T::Output>>>
+    where
+        R: AsyncRead + ?Sized,
+    {
+        loop {
+            if let Some(value) = self.decode()? {
+                return Poll::Ready(Ok(Some(value)));
+            }
+
+            let start = self.buf.len();
+            self.buf.resize(start + CHUNK_SIZE, 0);
+
+            let read = reader.as_mut().poll_read(cx, &mut self.buf[start..]);
+
+            match read {
+                Poll::Ready(Ok(0)) => {
+                    self.buf.truncate(start);
+
+                    if self.buffered() == 0 {
+                        return Poll::Ready(Ok(None));
+                    } else {
+                        return Poll::Ready(
+                            Err(ErrorKind::Io(io::ErrorKind::UnexpectedEof).into()),
+                        );
+                    }
+                }
+                Poll::Ready(Ok(n)) => self.buf.truncate(start + n),
+                Poll::Ready(Err(err)) => {
+                    self.buf.truncate(start);
+                    return Poll::Ready(Err(err.into()));
+                }
+                Poll::Pending => {
+                    self.buf.truncate(start);
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+
+    /// Reads next value from `reader`. Returns `Ok(None)` when `reader` reaches end of input on a frame boundary.
+    pub async fn read<R>(&mut self, reader: &mut R) -> Result<Option<T::Output>>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+    {
+        poll_fn(|cx| self.poll_read(cx, Pin::new(&mut *reader))).await
+    }
+}
+
+/// Returns length of frame from its length prefix
+#[inline]
+fn get_len(bytes: &[u8], max_frame_size: usize) -> Result<usize> {
+    let len = <u64 as DesseOwned>::deserialize_owned(bytes)?;
+
+    if len > max_frame_size as u64 {
+        return Err(ErrorKind::FrameTooLarge.into());
+    }
+
+    Ok(len as usize)
+}
+
+async fn write_all<W>(writer: &mut W, mut buf: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    while !buf.is_empty() {
+        let n = poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, buf)).await?;
+
+        if n == 0 {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+
+        buf = &buf[n..];
+    }
+
+    Ok(())
+}
+
+async fn read_exact<R>(reader: &mut R, buf: &mut [u8]) -> io::Result<()>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut filled = 0;
+
+    while filled < buf.len() {
+        let n = poll_fn(|cx| Pin::new(&mut *reader).poll_read(cx, &mut buf[filled..])).await?;
+
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        filled += n;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::VecDeque;
+    use alloc::string::{String, ToString};
+    use alloc::sync::Arc;
+    use alloc::vec;
+    use core::task::Waker;
+    use std::sync::Mutex;
+
+    use futures::executor::block_on;
+    use futures::future::join;
+
+    #[derive(Default)]
+    struct Pipe {
+        buf: VecDeque<u8>,
+        closed: bool,
+        reader: Option<Waker>,
+        writer: Option<Waker>,
+    }
+
+    /// One end of an in-memory duplex pipe with a small capacity
+    struct PipeEnd {
+        capacity: usize,
+        incoming: Arc<Mutex<Pipe>>,
+        outgoing: Arc<Mutex<Pipe>>,
+    }
+
+    fn duplex(capacity: usize) -> (PipeEnd, PipeEnd) {
+        let a = Arc::new(Mutex::new(Pipe::default()));
+        let b = Arc::new(Mutex::new(Pipe::default()));
+
+        (
+            PipeEnd {
+                capacity,
+                incoming: a.clone(),
+                outgoing: b.clone(),
+            },
+            PipeEnd {
+                capacity,
+                incoming: b,
+                outgoing: a,
+            },
+        )
+    }
+
+    impl AsyncRead for PipeEnd {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let mut pipe = self.incoming.lock().unwrap();
+
+            if pipe.buf.is_empty() {
+                if pipe.closed {
+                    return Poll::Ready(Ok(0));
+                }
+
+                pipe.reader = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+
+            let n = core::cmp::min(buf.len(), pipe.buf.len());
+
+            for (byte, value) in buf.iter_mut().zip(pipe.buf.drain(..n)) {
+                *byte = value;
+            }
+
+            if let Some(waker) = pipe.writer.take() {
+                waker.wake();
+            }
+
+            Poll::Ready(Ok(n))
+        }
+    }
+
+    impl AsyncWrite for PipeEnd {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<io::Result<usize>> {
+            let mut pipe = self.outgoing.lock().unwrap();
+            let n = core::cmp::min(buf.len(), self.capacity - pipe.buf.len());
+
+            if n == 0 {
+                pipe.writer = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+
+            pipe.buf.extend(&buf[..n]);
+
+            if let Some(waker) = pipe.reader.take() {
+                waker.wake();
+            }
+
+            Poll::Ready(Ok(n))
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+            let mut pipe = self.outgoing.lock().unwrap();
+            pipe.closed = true;
+
+            if let Some(waker) = pipe.reader.take() {
+                waker.wake();
+            }
+
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl Drop for PipeEnd {
+        fn drop(&mut self) {
+            let mut pipe = self.outgoing.lock().unwrap();
+            pipe.closed = true;
+
+            if let Some(waker) = pipe.reader.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn messages() -> Vec<Vec<String>> {
+        vec![
+            vec!["hello".to_string(), "world".to_string()],
+            vec![],
+            vec!["a".repeat(100)],
+        ]
+    }
+
+    #[test]
+    fn check_read_write_value() {
+        let (mut a, mut b) = duplex(3);
+
+        let write = async {
+            for message in messages() {
+                write_value(&mut a, &message).await.unwrap();
+            }
+        };
+        let read = async {
+            let mut received = Vec::new();
+
+            for _ in 0..messages().len() {
+                received.push(read_value::<Vec<String>, _>(&mut b).await.unwrap());
+            }
+
+            received
+        };
+
+        let (_, received) = block_on(join(write, read));
+        assert_eq!(messages(), received);
+    }
+
+    #[test]
+    fn check_decoder() {
+        let (mut a, mut b) = duplex(5);
+
+        let write = async move {
+            for message in messages() {
+                write_value(&mut a, &message).await.unwrap();
+            }
+        };
+        let read = async {
+            let mut decoder = Decoder::<Vec<String>>::new();
+            let mut received = Vec::new();
+
+            while let Some(message) = decoder.read(&mut b).await.unwrap() {
+                received.push(message);
+            }
+
+            received
+        };
+
+        let (_, received) = block_on(join(write, read));
+        assert_eq!(messages(), received);
+    }
+
+    #[test]
+    fn check_decoder_partial_input() {
+        let mut bytes = Vec::new();
+        block_on(write_value(&mut bytes_writer(&mut bytes), &"hello")).unwrap();
+
+        let mut decoder = Decoder::<String>::new();
+
+        for byte in &bytes[..(bytes.len() - 1)] {
+            decoder.extend(&[*byte]);
+            assert_eq!(None, decoder.decode().unwrap());
+        }
+
+        decoder.extend(&bytes[(bytes.len() - 1)..]);
+        assert_eq!(Some("hello".to_string()), decoder.decode().unwrap());
+        assert_eq!(0, decoder.buffered());
+
+        let mut partial = &bytes[..3];
+        let err = block_on(decoder.read(&mut partial)).unwrap_err();
+        assert_eq!(ErrorKind::Io(io::ErrorKind::UnexpectedEof), err.kind());
+    }
+
+    #[test]
+    fn check_decoder_invalid_frame() {
+        let mut decoder = Decoder::<String>::new();
+
+        let mut invalid = DesseDynamic::serialize(&9u64).unwrap();
+        invalid.extend(DesseDynamic::serialize(&vec![0xFFu8]).unwrap());
+        decoder.extend(&invalid);
+
+        let mut valid = Vec::new();
+        block_on(write_value(&mut bytes_writer(&mut valid), &"ok")).unwrap();
+        decoder.extend(&valid);
+
+        assert_eq!(ErrorKind::InvalidStr, decoder.decode().unwrap_err().kind());
+        assert_eq!(Some("ok".to_string()), decoder.decode().unwrap());
+    }
+
+    #[test]
+    fn check_frame_too_large() {
+        let mut decoder = Decoder::<Vec<u8>>::with_max_frame_size(8);
+        assert_eq!(8, decoder.max_frame_size());
+
+        decoder.extend(&DesseDynamic::serialize(&9u64).unwrap());
+        assert_eq!(
+            ErrorKind::FrameTooLarge,
+            decoder.decode().unwrap_err().kind()
+        );
+
+        let mut bytes = DesseDynamic::serialize(&(DEFAULT_MAX_FRAME_SIZE as u64 + 1)).unwrap();
+        let err = block_on(read_value::<Vec<u8>, _>(&mut &bytes[..])).unwrap_err();
+        assert_eq!(ErrorKind::FrameTooLarge, err.kind());
+
+        bytes.clear();
+        let value = vec![0u8; DEFAULT_MAX_FRAME_SIZE];
+        let err = block_on(write_value(&mut bytes_writer(&mut bytes), &value)).unwrap_err();
+        assert_eq!(ErrorKind::FrameTooLarge, err.kind());
+        assert!(bytes.is_empty());
+    }
+
+    #[test]
+    fn check_trailing_bytes() {
+        // Frame of 3 bytes containing a `u16`
+        let mut bytes = DesseDynamic::serialize(&3u64).unwrap();
+        bytes.extend_from_slice(&[1, 0, 0]);
+
+        let err = block_on(read_value::<u16, _>(&mut &bytes[..])).unwrap_err();
+        assert_eq!(ErrorKind::TrailingBytes { remaining: 1 }, err.kind());
+
+        let mut decoder = Decoder::<u16>::new();
+        decoder.extend(&bytes);
+        assert_eq!(
+            ErrorKind::TrailingBytes { remaining: 1 },
+            decoder.decode().unwrap_err().kind()
+        );
+        assert_eq!(0, decoder.buffered());
+    }
+
+    #[test]
+    fn check_decoder_compaction() {
+        let mut frame = Vec::new();
+        block_on(write_value(&mut bytes_writer(&mut frame), &7u32)).unwrap();
+
+        let mut decoder = Decoder::<u32>::new();
+
+        // Keep first byte of next frame buffered so that buffer never becomes empty
+        decoder.extend(&frame[..1]);
+
+        for _ in 0..(4 * CHUNK_SIZE) {
+            decoder.extend(&frame[1..]);
+            decoder.extend(&frame[..1]);
+
+            assert_eq!(Some(7), decoder.decode().unwrap());
+            assert_eq!(None, decoder.decode().unwrap());
+            assert_eq!(1, decoder.buffered());
+            assert!(decoder.buf.len() <= 2 * CHUNK_SIZE + frame.len());
+        }
+
+        decoder.extend(&frame[1..]);
+        assert_eq!(Some(7), decoder.decode().unwrap());
+        assert_eq!(0, decoder.buffered());
+    }
+
+    /// Returns an `AsyncWrite` which appends to `bytes`
+    fn bytes_writer(bytes: &mut Vec<u8>) -> futures::io::Cursor<&mut Vec<u8>> {
+        futures::io::Cursor::new(bytes)
+    }
+}
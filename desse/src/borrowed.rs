@@ -0,0 +1,343 @@
+#![cfg(feature = "dynamic")]
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use crate::desse_dynamic::{array_from_vec, initial_capacity};
+use crate::{DesseOwned, DesseSized, Error, ErrorKind, LimitedReader, Limits, Reader, Result};
+
+/// Trait for types which can be deserialized by borrowing from input bytes (zero-copy)
+///
+/// Strings and byte slices (`&'de str`, `&'de [u8]` and `Cow<'de, str>`) are returned as references into input instead
+/// of being copied into new allocations. Encoding is same as that of [`DesseDynamic`](crate::DesseDynamic), so, any
+/// value serialized with `DesseDynamic` can be deserialized with this trait.
+///
+/// This trait is implemented by `DesseDynamic` derive macro along with `DesseDynamic`. A derived type can have (at
+/// most) one lifetime parameter, which is used as lifetime of borrowed input. Fields whose type does not contain this
+/// lifetime are deserialized as owned values, so, they only need to implement `DesseDynamic` (and
+/// [`DesseOwned`](crate::DesseOwned)).
+///
+/// # Example
+///
+/// ```
+/// use desse::{DesseBorrowed, DesseDynamic};
+///
+/// #[derive(Debug, PartialEq, DesseDynamic)]
+/// struct Entry<'a> {
+///     key: &'a str,
+///     data: &'a [u8],
+/// }
+///
+/// let entry = Entry { key: "hello", data: &[1, 2, 3] };
+/// let bytes = entry.serialize().unwrap();
+///
+/// let new_entry = Entry::deserialize_borrowed(&bytes).unwrap();
+///
+/// assert_eq!(entry, new_entry);
+/// // `key` points into `bytes` (after its length prefix)
+/// assert_eq!(bytes[8..].as_ptr(), new_entry.key.as_ptr());
+/// ```
+pub trait DesseBorrowed<'de>: Sized {
+    /// Deserializes an object from the start of `reader` (borrowing from underlying slice wherever possible) and
+    /// advances `reader` past it
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::InvalidSliceLength`) when length of input slice is less than expected
+    /// and error (`ErrorKind::LimitExceeded`) when input exceeds any of the limits of `reader`.
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self>;
+
+    /// Deserializes an object from the start of byte slice, borrowing from it wherever possible
+    ///
+    /// Nesting depth of containers is limited to [`Limits::DEFAULT_MAX_DEPTH`](crate::Limits::DEFAULT_MAX_DEPTH) (use
+    /// `deserialize_borrowed_with_limits()` for a different limit).
+    #[inline]
+    fn deserialize_borrowed(bytes: &'de [u8]) -> Result<Self> {
+        Self::deserialize_borrowed_with_limits(bytes, &Limits::DEPTH_ONLY)
+    }
+
+    /// Deserializes an object from the start of byte slice, borrowing from it wherever possible, while enforcing
+    /// given [`Limits`](crate::Limits) (use this for untrusted input)
+    #[inline]
+    fn deserialize_borrowed_with_limits(bytes: &'de [u8], limits: &Limits) -> Result<Self> {
+        Self::deserialize_borrowed_from(&mut LimitedReader::new(bytes, *limits))
+    }
+}
+
+/// Reads a length prefix from `reader` and checks it using `check_len`
+#[inline]
+fn read_len<'de, F>(reader: &mut LimitedReader<&'de [u8]>, check_len: F) -> Result<u64>
+where
+    F: FnOnce(&mut LimitedReader<&'de [u8]>, u64) -> Result<()>,
+{
+    let len = <u64 as DesseOwned>::deserialize_owned(&mut *reader)?;
+    check_len(reader, len)?;
+    Ok(len)
+}
+
+macro_rules! impl_desse_borrowed_for_owned {
+    ($type: ty) => {
+        impl<'de> DesseBorrowed<'de> for $type {
+            #[inline]
+            fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+                <$type as DesseOwned>::deserialize_owned(reader)
+            }
+        }
+    };
+}
+
+impl_desse_borrowed_for_owned!(bool);
+impl_desse_borrowed_for_owned!(char);
+
+impl_desse_borrowed_for_owned!(u8);
+impl_desse_borrowed_for_owned!(u16);
+impl_desse_borrowed_for_owned!(u32);
+impl_desse_borrowed_for_owned!(u64);
+impl_desse_borrowed_for_owned!(u128);
+
+impl_desse_borrowed_for_owned!(i8);
+impl_desse_borrowed_for_owned!(i16);
+impl_desse_borrowed_for_owned!(i32);
+impl_desse_borrowed_for_owned!(i64);
+impl_desse_borrowed_for_owned!(i128);
+
+impl_desse_borrowed_for_owned!(Duration);
+impl_desse_borrowed_for_owned!(String);
+
+impl<'de> DesseBorrowed<'de> for &'de str {
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        let len = read_len(reader, LimitedReader::check_string_len)?;
+        let bytes = reader.read_borrowed(len as usize)?;
+
+        core::str::from_utf8(bytes).map_err(|err| {
+            let offset = <u64>::SIZE + err.valid_up_to();
+
+            Error::from(err)
+                .with_type_name(core::any::type_name::<&str>())
+                .with_offset(offset)
+        })
+    }
+}
+
+impl<'de> DesseBorrowed<'de> for Cow<'de, str> {
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        <&'de str as DesseBorrowed<'de>>::deserialize_borrowed_from(reader).map(Cow::Borrowed)
+    }
+}
+
+impl<'de> DesseBorrowed<'de> for &'de [u8] {
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        let len = read_len(reader, LimitedReader::check_sequence_len)?;
+        reader.read_borrowed(len as usize)
+    }
+}
+
+impl<'de, T> DesseBorrowed<'de> for Vec<T>
+where
+    T: DesseBorrowed<'de>,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        let start = reader.bytes_read();
+        let len = read_len(reader, LimitedReader::check_sequence_len)?;
+
+        reader.enter_nested()?;
+
+        let mut output = Vec::with_capacity(initial_capacity::<T>(len));
+
+        for i in 0..len {
+            let offset = reader.bytes_read() - start;
+
+            match T::deserialize_borrowed_from(reader) {
+                Ok(element) => output.push(element),
+                Err(err) => {
+                    reader.exit_nested();
+
+                    return Err(err
+                        .with_type_name(core::any::type_name::<T>())
+                        .with_index(i as usize)
+                        .with_offset(offset));
+                }
+            }
+        }
+
+        reader.exit_nested();
+
+        Ok(output)
+    }
+}
+
+impl<'de, T, const N: usize> DesseBorrowed<'de> for [T; N]
+where
+    T: DesseBorrowed<'de>,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        let start = reader.bytes_read();
+
+        reader.enter_nested()?;
+
+        let mut output = Vec::with_capacity(N);
+
+        for i in 0..N {
+            let offset = reader.bytes_read() - start;
+
+            match T::deserialize_borrowed_from(reader) {
+                Ok(element) => output.push(element),
+                Err(err) => {
+                    reader.exit_nested();
+
+                    return Err(err
+                        .with_type_name(core::any::type_name::<T>())
+                        .with_index(i)
+                        .with_offset(offset));
+                }
+            }
+        }
+
+        reader.exit_nested();
+
+        Ok(array_from_vec(output))
+    }
+}
+
+impl<'de, T> DesseBorrowed<'de> for Option<T>
+where
+    T: DesseBorrowed<'de>,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        match <u8 as DesseOwned>::deserialize_owned(&mut *reader)? {
+            0 => Ok(None),
+            1 => {
+                reader.enter_nested()?;
+                let value = T::deserialize_borrowed_from(reader);
+                reader.exit_nested();
+
+                value.map(Some).map_err(|err| {
+                    err.with_type_name(core::any::type_name::<T>())
+                        .with_offset(<u8>::SIZE)
+                })
+            }
+            _ => Err(Error::from(ErrorKind::InvalidInput)
+                .with_type_name(core::any::type_name::<Option<T>>())),
+        }
+    }
+}
+
+impl<'de, T, E> DesseBorrowed<'de> for core::result::Result<T, E>
+where
+    T: DesseBorrowed<'de>,
+    E: DesseBorrowed<'de>,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        let tag = <u8 as DesseOwned>::deserialize_owned(&mut *reader)?;
+
+        if tag > 1 {
+            return Err(Error::from(ErrorKind::InvalidTag)
+                .with_type_name(core::any::type_name::<core::result::Result<T, E>>())
+                .with_offset(0));
+        }
+
+        reader.enter_nested()?;
+
+        let value = if tag == 0 {
+            T::deserialize_borrowed_from(reader).map(Ok).map_err(|err| {
+                err.with_type_name(core::any::type_name::<T>())
+                    .with_offset(<u8>::SIZE)
+            })
+        } else {
+            E::deserialize_borrowed_from(reader)
+                .map(Err)
+                .map_err(|err| {
+                    err.with_type_name(core::any::type_name::<E>())
+                        .with_offset(<u8>::SIZE)
+                })
+        };
+
+        reader.exit_nested();
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // For initializing global memory allocator
+    extern crate std;
+
+    use super::*;
+
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    use crate::DesseDynamic;
+
+    #[test]
+    fn check_borrowed_str() {
+        let bytes = DesseDynamic::serialize(&"hello").unwrap();
+        let s = <&str>::deserialize_borrowed(&bytes).unwrap();
+
+        assert_eq!("hello", s);
+        assert_eq!(bytes[8..].as_ptr(), s.as_ptr());
+
+        match Cow::<str>::deserialize_borrowed(&bytes).unwrap() {
+            Cow::Borrowed(s) => assert_eq!("hello", s),
+            Cow::Owned(_) => panic!("Expected borrowed string"),
+        }
+    }
+
+    #[test]
+    fn check_borrowed_bytes() {
+        let bytes = DesseDynamic::serialize(&vec![1u8, 2, 3]).unwrap();
+        assert_eq!(&[1, 2, 3], <&[u8]>::deserialize_borrowed(&bytes).unwrap());
+
+        let bytes = DesseDynamic::serialize(&&[4u8, 5][..]).unwrap();
+        assert_eq!(vec![4, 5], Vec::<u8>::deserialize_from(&*bytes).unwrap());
+
+        let err = <&[u8]>::deserialize_borrowed(&bytes[..9]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+    }
+
+    #[test]
+    fn check_borrowed_reader_is_advanced() {
+        let mut bytes = DesseDynamic::serialize(&"a").unwrap();
+        bytes.extend(DesseDynamic::serialize(&Some(7u16)).unwrap());
+
+        let mut reader = LimitedReader::new(&*bytes, Limits::new());
+
+        assert_eq!("a", <&str>::deserialize_borrowed_from(&mut reader).unwrap());
+        assert_eq!(
+            Some(7),
+            Option::<u16>::deserialize_borrowed_from(&mut reader).unwrap()
+        );
+        assert_eq!(bytes.len(), reader.bytes_read());
+        assert!(reader.into_inner().is_empty());
+    }
+
+    #[test]
+    fn check_borrowed_vec_error_context() {
+        let mut bytes = DesseDynamic::serialize(&vec!["ab", "cd"]).unwrap();
+        bytes[8 + 10 + 8 + 1] = 0xFF;
+
+        let err = Vec::<&str>::deserialize_borrowed(&bytes).unwrap_err();
+
+        assert_eq!(ErrorKind::InvalidStr, err.kind());
+        assert_eq!(Some(8 + 10 + 8 + 1), err.offset());
+        assert_eq!("[1]", err.path().to_string());
+    }
+
+    #[test]
+    fn check_borrowed_result() {
+        let bytes = DesseDynamic::serialize(&Err::<u32, &str>("failed")).unwrap();
+        let result = core::result::Result::<u32, &str>::deserialize_borrowed(&bytes).unwrap();
+
+        assert_eq!(Err("failed"), result);
+        assert_eq!(bytes[1 + 8..].as_ptr(), result.unwrap_err().as_ptr());
+    }
+}
@@ -0,0 +1,498 @@
+#![cfg(feature = "c-header")]
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt::{Display, Formatter, Write};
+use core::time::Duration;
+
+use crate::DesseSized;
+
+/// Any type must implement this trait for being described in a generated C header
+pub trait DesseCType: DesseSized {
+    /// Name of C type (element type in case of arrays)
+    const C_TYPE: &'static str;
+
+    /// Number of elements when current type is a fixed size array
+    const C_ARRAY_LEN: Option<usize> = None;
+
+    /// Unsigned C integer type used by accessor functions to load and store current type in little-endian byte order
+    /// (`None` for aggregate types)
+    const C_SCALAR: Option<&'static str> = None;
+
+    /// Emits declarations of current type (and all the types it depends on) in given header
+    #[inline]
+    fn c_declare(header: &mut CHeader) {
+        let _ = header;
+    }
+}
+
+/// Field of a C `struct` (used by [`CHeader`](self::CHeader) while generating definitions)
+#[derive(Debug, Clone, Copy)]
+pub struct CField {
+    name: &'static str,
+    c_type: &'static str,
+    array_len: Option<usize>,
+    scalar: Option<&'static str>,
+}
+
+impl CField {
+    /// Creates a new field with given name and type
+    #[inline]
+    pub fn new<T: DesseCType>(name: &'static str) -> Self {
+        Self {
+            name,
+            c_type: T::C_TYPE,
+            array_len: T::C_ARRAY_LEN,
+            scalar: T::C_SCALAR,
+        }
+    }
+
+    /// Creates a new field for `len` reserved bytes
+    #[inline]
+    pub fn reserved(len: usize) -> Self {
+        Self {
+            name: "_reserved",
+            c_type: "uint8_t",
+            array_len: Some(len),
+            scalar: None,
+        }
+    }
+}
+
+/// Variant of a tagged union (used by [`CHeader`](self::CHeader) while generating definitions)
+#[derive(Debug, Clone, Copy)]
+pub struct CVariant<'a> {
+    name: &'static str,
+    tag: u64,
+    fields: &'a [CField],
+}
+
+impl<'a> CVariant<'a> {
+    /// Creates a new variant with given name, tag and fields
+    #[inline]
+    pub fn new(name: &'static str, tag: u64, fields: &'a [CField]) -> Self {
+        Self { name, tag, fields }
+    }
+}
+
+/// Generator for C header containing `__attribute__((packed))` definitions of [`DesseCType`](self::DesseCType) types
+///
+/// # Example
+///
+/// ```
+/// use desse::{CHeader, DesseCType, DesseSized};
+///
+/// #[derive(DesseSized, DesseCType)]
+/// struct MyStruct {
+///     a: u8,
+///     b: u16,
+/// }
+///
+/// let mut header = CHeader::new("MY_STRUCT_H");
+/// header.add::<MyStruct>();
+///
+/// let header = header.to_string();
+///
+/// assert!(header.contains("#define MY_STRUCT_SIZE 3"));
+/// assert!(header.contains("static inline uint16_t MyStruct_get_b(const MyStruct *p)"));
+/// ```
+#[derive(Debug)]
+pub struct CHeader {
+    guard: String,
+    /// Rust type (full path) of each declared C type
+    declared: BTreeMap<&'static str, &'static str>,
+    body: String,
+}
+
+impl CHeader {
+    /// Creates a new empty header with given include guard
+    pub fn new(guard: &str) -> Self {
+        Self {
+            guard: guard.into(),
+            declared: BTreeMap::new(),
+            body: String::new(),
+        }
+    }
+
+    /// Adds definition of `T` (and all the types it depends on) to current header
+    pub fn add<T: DesseCType>(&mut self) -> &mut Self {
+        T::c_declare(self);
+        self
+    }
+
+    /// Marks `T` as declared. Returns `false` if it was already declared.
+    ///
+    /// # Panic
+    ///
+    /// Panics if a different type with same C type name (e.g., a type with same name from another module) was already
+    /// declared, since both the definitions cannot be emitted in one header.
+    pub fn declare<T: DesseCType>(&mut self) -> bool {
+        let path = core::any::type_name::<T>();
+
+        match self.declared.insert(T::C_TYPE, path) {
+            None => true,
+            Some(declared) if declared == path => false,
+            Some(declared) => panic!(
+                "C type `{}` is declared by both `{}` and `{}`",
+                T::C_TYPE,
+                declared,
+                path
+            ),
+        }
+    }
+
+    /// Emits definition of a `struct` with given fields, its size constant and its accessor functions
+    pub fn define_struct(&mut self, name: &str, size: usize, fields: &[CField]) {
+        self.push_line(format_args!(
+            "typedef struct __attribute__((packed)) {} {{",
+            name
+        ));
+        self.push_fields(fields, 1);
+        self.push_line(format_args!("}} {};", name));
+        self.push_size(name, size);
+
+        for field in fields {
+            self.push_accessors(name, name, field.name, field);
+        }
+    }
+
+    /// Emits definition of a tagged union with given variants, its size and tag constants and its accessor functions.
+    /// `header` fields (e.g., layout version) are placed before `tag` and `trailer` fields (e.g., checksum) are placed
+    /// after payload of variants.
+    pub fn define_enum(
+        &mut self,
+        name: &str,
+        size: usize,
+        header: &[CField],
+        tag: CField,
+        variants: &[CVariant<'_>],
+        trailer: &[CField],
+    ) {
+        self.push_line(format_args!(
+            "typedef struct __attribute__((packed)) {} {{",
+            name
+        ));
+        self.push_fields(header, 1);
+        self.push_fields(&[tag], 1);
+
+        if variants.iter().any(|variant| !variant.fields.is_empty()) {
+            self.push_line(format_args!("    union __attribute__((packed)) {{"));
+
+            for variant in variants.iter().filter(|variant| !variant.fields.is_empty()) {
+                self.push_line(format_args!("        struct __attribute__((packed)) {{"));
+                self.push_fields(variant.fields, 3);
+                self.push_line(format_args!("        }} {};", variant.name));
+            }
+
+            self.push_line(format_args!("    }} payload;"));
+        }
+
+        self.push_fields(trailer, 1);
+        self.push_line(format_args!("}} {};", name));
+        self.push_size(name, size);
+
+        for variant in variants {
+            self.push_line(format_args!(
+                "#define {}_{} {}",
+                ScreamingSnake(name),
+                ScreamingSnake(variant.name),
+                variant.tag
+            ));
+        }
+
+        for field in header.iter().chain(Some(&tag)).chain(trailer) {
+            self.push_accessors(name, name, field.name, field);
+        }
+
+        for variant in variants {
+            let mut prefix = String::new();
+            let _ = write!(prefix, "{}_{}", name, variant.name);
+
+            for field in variant.fields {
+                let mut member = String::new();
+                let _ = write!(member, "payload.{}.{}", variant.name, field.name);
+
+                self.push_accessors(name, &prefix, &member, field);
+            }
+        }
+    }
+
+    fn push_line(&mut self, args: core::fmt::Arguments<'_>) {
+        let _ = self.body.write_fmt(args);
+        self.body.push('\n');
+    }
+
+    fn push_fields(&mut self, fields: &[CField], indent: usize) {
+        for field in fields {
+            for _ in 0..indent {
+                self.body.push_str("    ");
+            }
+
+            match field.array_len {
+                None => self.push_line(format_args!("{} {};", field.c_type, field.name)),
+                Some(len) => {
+                    self.push_line(format_args!("{} {}[{}];", field.c_type, field.name, len))
+                }
+            }
+        }
+    }
+
+    fn push_size(&mut self, name: &str, size: usize) {
+        self.push_line(format_args!(""));
+        self.push_line(format_args!(
+            "#define {}_SIZE {}",
+            ScreamingSnake(name),
+            size
+        ));
+        self.push_line(format_args!(
+            "_Static_assert(sizeof({}) == {}_SIZE, \"Size of {} does not match desse layout\");",
+            name,
+            ScreamingSnake(name),
+            name
+        ));
+    }
+
+    fn push_accessors(&mut self, name: &str, prefix: &str, member: &str, field: &CField) {
+        let repr = match (field.scalar, field.array_len) {
+            (Some(repr), None) => repr,
+            _ => return,
+        };
+        let c_type = field.c_type;
+        let function = field.name;
+
+        self.push_line(format_args!(""));
+        self.push_line(format_args!(
+            "static inline {} {}_get_{}(const {} *p) {{",
+            c_type, prefix, function, name
+        ));
+        self.push_line(format_args!(
+            "    const uint8_t *b = (const uint8_t *)p + offsetof({}, {});",
+            name, member
+        ));
+        self.push_line(format_args!("    {} v = 0;", repr));
+        self.push_line(format_args!(
+            "    for (size_t i = 0; i < sizeof(v); i++) {{"
+        ));
+        self.push_line(format_args!("        v |= ({})b[i] << (8 * i);", repr));
+        self.push_line(format_args!("    }}"));
+        self.push_line(format_args!("    return ({})v;", c_type));
+        self.push_line(format_args!("}}"));
+        self.push_line(format_args!(""));
+        self.push_line(format_args!(
+            "static inline void {}_set_{}({} *p, {} value) {{",
+            prefix, function, name, c_type
+        ));
+        self.push_line(format_args!(
+            "    uint8_t *b = (uint8_t *)p + offsetof({}, {});",
+            name, member
+        ));
+        self.push_line(format_args!("    {} v = ({})value;", repr, repr));
+        self.push_line(format_args!(
+            "    for (size_t i = 0; i < sizeof(v); i++) {{"
+        ));
+        self.push_line(format_args!("        b[i] = (uint8_t)(v >> (8 * i));"));
+        self.push_line(format_args!("    }}"));
+        self.push_line(format_args!("}}"));
+    }
+}
+
+impl Display for CHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "/* Generated by desse. Do not edit. */")?;
+        writeln!(f, "#ifndef {}", self.guard)?;
+        writeln!(f, "#define {}", self.guard)?;
+        writeln!(f)?;
+        writeln!(f, "#include <stddef.h>")?;
+        writeln!(f, "#include <stdint.h>")?;
+        writeln!(f)?;
+        write!(f, "{}", self.body)?;
+        writeln!(f)?;
+        writeln!(f, "#endif /* {} */", self.guard)
+    }
+}
+
+/// Formats an identifier (`CamelCase` or `snake_case`) in `SCREAMING_SNAKE_CASE`
+struct ScreamingSnake<'a>(&'a str);
+
+impl Display for ScreamingSnake<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut previous: Option<char> = None;
+
+        for c in self.0.chars() {
+            if let Some(previous) = previous {
+                if c.is_uppercase() && (previous.is_lowercase() || previous.is_numeric()) {
+                    f.write_char('_')?;
+                }
+            }
+
+            for upper in c.to_uppercase() {
+                f.write_char(upper)?;
+            }
+
+            previous = Some(c);
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! impl_desse_c_type {
+    ($type: ty, $c_type: expr, $scalar: expr) => {
+        impl DesseCType for $type {
+            const C_TYPE: &'static str = $c_type;
+            const C_SCALAR: Option<&'static str> = Some($scalar);
+        }
+    };
+}
+
+macro_rules! impl_desse_c_type_arr {
+    ($num: expr) => {
+        impl<T> DesseCType for [T; $num]
+        where
+            T: DesseCType,
+        {
+            const C_TYPE: &'static str = T::C_TYPE;
+            const C_ARRAY_LEN: Option<usize> = Some($num);
+
+            #[inline]
+            fn c_declare(header: &mut CHeader) {
+                T::c_declare(header)
+            }
+        }
+    };
+}
+
+impl_desse_c_type!(bool, "uint8_t", "uint8_t");
+impl_desse_c_type!(char, "uint32_t", "uint32_t");
+
+impl_desse_c_type!(u8, "uint8_t", "uint8_t");
+impl_desse_c_type!(u16, "uint16_t", "uint16_t");
+impl_desse_c_type!(u32, "uint32_t", "uint32_t");
+impl_desse_c_type!(u64, "uint64_t", "uint64_t");
+impl_desse_c_type!(u128, "unsigned __int128", "unsigned __int128");
+
+impl_desse_c_type!(i8, "int8_t", "uint8_t");
+impl_desse_c_type!(i16, "int16_t", "uint16_t");
+impl_desse_c_type!(i32, "int32_t", "uint32_t");
+impl_desse_c_type!(i64, "int64_t", "uint64_t");
+impl_desse_c_type!(i128, "__int128", "unsigned __int128");
+
+impl DesseCType for Duration {
+    const C_TYPE: &'static str = "desse_duration";
+
+    fn c_declare(header: &mut CHeader) {
+        if header.declare::<Self>() {
+            header.define_struct(
+                Self::C_TYPE,
+                Self::SIZE,
+                &[CField::new::<u64>("secs"), CField::new::<u32>("nanos")],
+            );
+        }
+    }
+}
+
+impl_desse_c_type_arr!(1);
+impl_desse_c_type_arr!(2);
+impl_desse_c_type_arr!(3);
+impl_desse_c_type_arr!(4);
+impl_desse_c_type_arr!(5);
+impl_desse_c_type_arr!(6);
+impl_desse_c_type_arr!(7);
+impl_desse_c_type_arr!(8);
+impl_desse_c_type_arr!(9);
+impl_desse_c_type_arr!(10);
+impl_desse_c_type_arr!(11);
+impl_desse_c_type_arr!(12);
+impl_desse_c_type_arr!(13);
+impl_desse_c_type_arr!(14);
+impl_desse_c_type_arr!(15);
+impl_desse_c_type_arr!(16);
+impl_desse_c_type_arr!(17);
+impl_desse_c_type_arr!(18);
+impl_desse_c_type_arr!(19);
+impl_desse_c_type_arr!(20);
+impl_desse_c_type_arr!(21);
+impl_desse_c_type_arr!(22);
+impl_desse_c_type_arr!(23);
+impl_desse_c_type_arr!(24);
+impl_desse_c_type_arr!(25);
+impl_desse_c_type_arr!(26);
+impl_desse_c_type_arr!(27);
+impl_desse_c_type_arr!(28);
+impl_desse_c_type_arr!(29);
+impl_desse_c_type_arr!(30);
+impl_desse_c_type_arr!(31);
+impl_desse_c_type_arr!(32);
+
+#[cfg(test)]
+mod tests {
+    // For initializing global memory allocator
+    extern crate std;
+
+    use super::*;
+
+    use alloc::string::ToString;
+
+    #[test]
+    fn check_screaming_snake() {
+        assert_eq!("MY_STRUCT", ScreamingSnake("MyStruct").to_string());
+        assert_eq!("VARIANT1", ScreamingSnake("Variant1").to_string());
+        assert_eq!(
+            "DESSE_DURATION",
+            ScreamingSnake("desse_duration").to_string()
+        );
+        assert_eq!("IPV4_ADDR", ScreamingSnake("Ipv4Addr").to_string());
+    }
+
+    #[test]
+    fn check_duration_header() {
+        let mut header = CHeader::new("DURATION_H");
+        header.add::<Duration>().add::<Duration>();
+
+        let header = header.to_string();
+
+        assert_eq!(1, header.matches("typedef struct").count());
+        assert!(header.contains("    uint64_t secs;\n    uint32_t nanos;\n"));
+        assert!(header.contains("#define DESSE_DURATION_SIZE 12"));
+        assert!(header.contains("static inline uint32_t desse_duration_get_nanos("));
+    }
+
+    /// Defines a `u8` wrapper with C type name `Point`
+    macro_rules! point {
+        ($module: ident) => {
+            mod $module {
+                use crate::{CField, CHeader, DesseCType, DesseSized};
+
+                pub struct Point;
+
+                impl DesseSized for Point {
+                    const SIZE: usize = 1;
+                }
+
+                impl DesseCType for Point {
+                    const C_TYPE: &'static str = "Point";
+
+                    fn c_declare(header: &mut CHeader) {
+                        if header.declare::<Self>() {
+                            header.define_struct(
+                                Self::C_TYPE,
+                                Self::SIZE,
+                                &[CField::new::<u8>("x")],
+                            );
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    point!(first);
+    point!(second);
+
+    #[test]
+    #[should_panic(expected = "C type `Point` is declared by both")]
+    fn check_conflicting_names() {
+        let mut header = CHeader::new("POINT_H");
+        header.add::<first::Point>().add::<first::Point>();
+        header.add::<second::Point>();
+    }
+}
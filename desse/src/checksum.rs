@@ -0,0 +1,432 @@
+#[cfg(feature = "dynamic")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "dynamic")]
+use crate::private::Sealed;
+#[cfg(feature = "dynamic")]
+use crate::{
+    DesseBorrowed, DesseDynamic, DesseOwned, DesseSized, Error, ErrorKind, LimitedReader, Reader,
+    Result, Writer,
+};
+
+/// Lookup table for CRC32C (Castagnoli polynomial, reflected: `0x82F63B78`)
+const TABLE: [u32; 256] = make_table();
+
+const fn make_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82F6_3B78
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+/// Incremental CRC32C (Castagnoli) hasher
+///
+/// # Example
+///
+/// ```
+/// use desse::{crc32c, Crc32c};
+///
+/// let mut hasher = Crc32c::new();
+/// hasher.update(b"1234");
+/// hasher.update(b"56789");
+///
+/// assert_eq!(0xE306_9283, hasher.finish());
+/// assert_eq!(crc32c(b"123456789"), hasher.finish());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32c {
+    state: u32,
+}
+
+impl Default for Crc32c {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crc32c {
+    /// Creates a new hasher
+    #[inline]
+    pub const fn new() -> Self {
+        Self { state: !0 }
+    }
+
+    /// Updates checksum with given bytes
+    #[inline]
+    pub fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state =
+                TABLE[((self.state ^ u32::from(*byte)) & 0xFF) as usize] ^ (self.state >> 8);
+        }
+    }
+
+    /// Returns checksum of all the bytes passed to `update()` so far
+    #[inline]
+    pub fn finish(&self) -> u32 {
+        !self.state
+    }
+}
+
+/// Returns CRC32C (Castagnoli) checksum of given bytes
+#[inline]
+pub fn crc32c(bytes: &[u8]) -> u32 {
+    let mut hasher = Crc32c::new();
+    hasher.update(bytes);
+    hasher.finish()
+}
+
+/// Wrapper for [`DesseDynamic`](crate::DesseDynamic) values which are serialized with a CRC32C trailer
+///
+/// Value is serialized as usual and followed by CRC32C (`u32`, little endian) of its serialized bytes. Deserialization
+/// verifies the trailer and returns error (`ErrorKind::ChecksumMismatch`) when it does not match. This can be used for
+/// whole payloads persisted on disk as well as for individual fields of a derived type.
+///
+/// # Example
+///
+/// ```
+/// use desse::{Checksummed, DesseDynamic, ErrorKind};
+///
+/// let value = Checksummed(String::from("hello"));
+/// let mut bytes = value.serialize().unwrap();
+///
+/// // Length prefix and bytes of string followed by checksum
+/// assert_eq!(8 + 5 + 4, bytes.len());
+/// assert_eq!(value, Checksummed::<String>::deserialize_exact(&bytes).unwrap());
+///
+/// bytes[8] ^= 1;
+///
+/// let err = Checksummed::<String>::deserialize_exact(&bytes).unwrap_err();
+/// assert_eq!(ErrorKind::ChecksumMismatch, err.kind());
+/// ```
+#[cfg(feature = "dynamic")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checksummed<T>(pub T);
+
+/// Returns error for a checksum trailer which does not match value of type `T`
+#[cfg(feature = "dynamic")]
+#[cold]
+fn checksum_mismatch<T>() -> Error {
+    Error::from(ErrorKind::ChecksumMismatch)
+        .with_type_name(core::any::type_name::<Checksummed<T>>())
+}
+
+#[cfg(feature = "dynamic")]
+impl<T> DesseDynamic for Checksummed<T>
+where
+    T: DesseDynamic,
+{
+    type Output = Checksummed<T::Output>;
+
+    // Not derived from `T::MIN_SERIALIZED_SIZE` so that recursive types (through `Box`) do not form a cycle
+    const MIN_SERIALIZED_SIZE: usize = <u32>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        self.0.serialized_size() + <u32>::SIZE
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(self.serialized_size());
+        self.serialize_into_unchecked(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, mut writer: W) -> Result<()> {
+        let mut checksum_writer = ChecksumWriter::new(writer.inner_mut());
+        self.0.serialize_into(&mut checksum_writer)?;
+
+        let checksum = checksum_writer.finish();
+        DesseDynamic::serialize_into(&checksum, writer.inner_mut())
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, mut writer: W) -> Result<()> {
+        let mut checksum_writer = ChecksumWriter::new(writer.inner_mut());
+        self.0.serialize_into_unchecked(&mut checksum_writer)?;
+
+        let checksum = checksum_writer.finish();
+        DesseDynamic::serialize_into_unchecked(&checksum, writer.inner_mut())
+    }
+}
+
+#[cfg(feature = "dynamic")]
+impl<T> DesseOwned for Checksummed<T>
+where
+    T: DesseOwned,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(mut reader: R) -> Result<Self::Output> {
+        let mut checksum_reader = ChecksumReader::new(reader.inner_mut());
+        let value = T::deserialize_owned(&mut checksum_reader)?;
+        let checksum = checksum_reader.finish();
+
+        if checksum != <u32 as DesseOwned>::deserialize_owned(reader.inner_mut())? {
+            return Err(checksum_mismatch::<T::Output>());
+        }
+
+        Ok(Checksummed(value))
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
+        let mut checksum_reader = ChecksumReader::new(reader.inner_mut());
+        let value = T::deserialize_owned_unchecked(&mut checksum_reader)?;
+        let checksum = checksum_reader.finish();
+
+        if checksum != <u32 as DesseOwned>::deserialize_owned_unchecked(reader.inner_mut())? {
+            return Err(checksum_mismatch::<T::Output>());
+        }
+
+        Ok(Checksummed(value))
+    }
+}
+
+#[cfg(feature = "dynamic")]
+impl<'de, T> DesseBorrowed<'de> for Checksummed<T>
+where
+    T: DesseBorrowed<'de>,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        let bytes = reader.remaining();
+        let start = reader.bytes_read();
+
+        let value = T::deserialize_borrowed_from(reader)?;
+        let checksum = crc32c(&bytes[..reader.bytes_read() - start]);
+
+        if checksum != <u32 as DesseOwned>::deserialize_owned(&mut *reader)? {
+            return Err(checksum_mismatch::<T>());
+        }
+
+        Ok(Checksummed(value))
+    }
+}
+
+/// Writer which computes CRC32C of all the bytes written in underlying writer
+#[cfg(feature = "dynamic")]
+pub(crate) struct ChecksumWriter<'a, W: Writer + ?Sized> {
+    inner: &'a mut W,
+    hasher: Crc32c,
+}
+
+#[cfg(feature = "dynamic")]
+impl<'a, W: Writer + ?Sized> ChecksumWriter<'a, W> {
+    /// Creates a new checksum writer
+    #[inline]
+    pub(crate) fn new(inner: &'a mut W) -> Self {
+        Self {
+            inner,
+            hasher: Crc32c::new(),
+        }
+    }
+
+    /// Returns checksum of all the bytes written so far
+    #[inline]
+    pub(crate) fn finish(&self) -> u32 {
+        self.hasher.finish()
+    }
+}
+
+#[cfg(feature = "dynamic")]
+impl<W: Writer + ?Sized> Sealed for ChecksumWriter<'_, W> {}
+
+#[cfg(feature = "dynamic")]
+impl<W: Writer + ?Sized> Writer for ChecksumWriter<'_, W> {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<()> {
+        self.inner.write(buf)?;
+        self.hasher.update(buf);
+        Ok(())
+    }
+
+    #[inline]
+    fn write_unchecked(&mut self, buf: &[u8]) -> Result<()> {
+        self.inner.write_unchecked(buf)?;
+        self.hasher.update(buf);
+        Ok(())
+    }
+}
+
+/// Reader which computes CRC32C of all the bytes read from underlying reader
+#[cfg(feature = "dynamic")]
+struct ChecksumReader<'a, R: Reader> {
+    inner: &'a mut R,
+    hasher: Crc32c,
+}
+
+#[cfg(feature = "dynamic")]
+impl<'a, R: Reader> ChecksumReader<'a, R> {
+    /// Creates a new checksum reader
+    #[inline]
+    fn new(inner: &'a mut R) -> Self {
+        Self {
+            inner,
+            hasher: Crc32c::new(),
+        }
+    }
+
+    /// Returns checksum of all the bytes read so far
+    #[inline]
+    fn finish(&self) -> u32 {
+        self.hasher.finish()
+    }
+}
+
+#[cfg(feature = "dynamic")]
+impl<R: Reader> Sealed for ChecksumReader<'_, R> {}
+
+#[cfg(feature = "dynamic")]
+impl<R: Reader> Reader for ChecksumReader<'_, R> {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
+    #[inline]
+    fn read(&mut self, len: usize) -> Result<&[u8]> {
+        let bytes = self.inner.read(len)?;
+        self.hasher.update(bytes);
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn read_unchecked(&mut self, len: usize) -> Result<&[u8]> {
+        let bytes = self.inner.read_unchecked(len)?;
+        self.hasher.update(bytes);
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn is_at_end(&mut self) -> Result<bool> {
+        self.inner.is_at_end()
+    }
+
+    #[inline]
+    fn ensure(&mut self, len: usize) -> Result<()> {
+        self.inner.ensure(len)
+    }
+
+    #[inline]
+    fn check_sequence_len(&mut self, len: u64) -> Result<()> {
+        self.inner.check_sequence_len(len)
+    }
+
+    #[inline]
+    fn check_string_len(&mut self, len: u64) -> Result<()> {
+        self.inner.check_string_len(len)
+    }
+
+    #[inline]
+    fn enter_nested(&mut self) -> Result<()> {
+        self.inner.enter_nested()
+    }
+
+    #[inline]
+    fn exit_nested(&mut self) {
+        self.inner.exit_nested()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_crc32c() {
+        assert_eq!(0, crc32c(b""));
+        assert_eq!(0xE306_9283, crc32c(b"123456789"));
+        assert_eq!(0x8A91_36AA, crc32c(&[0; 32]));
+        assert_eq!(0x62A8_AB43, crc32c(&[0xFF; 32]));
+    }
+
+    #[test]
+    fn check_crc32c_incremental() {
+        let bytes: [u8; 64] = {
+            let mut bytes = [0; 64];
+
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = i as u8;
+            }
+
+            bytes
+        };
+
+        for split in 0..bytes.len() {
+            let mut hasher = Crc32c::new();
+            hasher.update(&bytes[..split]);
+            hasher.update(&bytes[split..]);
+
+            assert_eq!(crc32c(&bytes), hasher.finish());
+        }
+    }
+
+    #[cfg(feature = "dynamic")]
+    #[test]
+    fn check_checksummed() {
+        // For initializing global memory allocator
+        extern crate std;
+
+        use alloc::string::String;
+        use alloc::vec;
+
+        let value = Checksummed(vec![String::from("hello"), String::from("world")]);
+        let mut bytes = value.serialize().unwrap();
+
+        assert_eq!(value.serialized_size(), bytes.len());
+        assert_eq!(
+            crc32c(&bytes[..bytes.len() - 4]).to_le_bytes(),
+            bytes[bytes.len() - 4..]
+        );
+        assert_eq!(
+            value,
+            Checksummed::<Vec<String>>::deserialize_exact(&bytes).unwrap()
+        );
+        assert_eq!(
+            Checksummed(vec!["hello", "world"]),
+            Checksummed::<Vec<&str>>::deserialize_borrowed(&bytes).unwrap()
+        );
+
+        let mut writer = Vec::new();
+        value.serialize_into(&mut writer).unwrap();
+        assert_eq!(bytes, writer);
+
+        // Corrupt last byte of "world"
+        let index = bytes.len() - 5;
+        bytes[index] ^= 0x20;
+
+        let err = Checksummed::<Vec<String>>::deserialize_exact(&bytes).unwrap_err();
+        assert_eq!(ErrorKind::ChecksumMismatch, err.kind());
+
+        let err = Checksummed::<Vec<&str>>::deserialize_borrowed(&bytes).unwrap_err();
+        assert_eq!(ErrorKind::ChecksumMismatch, err.kind());
+    }
+}
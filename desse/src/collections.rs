@@ -0,0 +1,1192 @@
+#![cfg(feature = "dynamic")]
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+use crate::desse_dynamic::{ensure_remaining, initial_capacity};
+use crate::{
+    DesseBorrowed, DesseDynamic, DesseOwned, DesseSized, Error, LimitedReader, Reader, Result,
+    Writer,
+};
+
+/// Adds context of element (or entry) at `index` of a collection, which starts at `offset` in serialized collection,
+/// to `err`
+#[cold]
+fn entry_error<T: ?Sized>(err: Error, offset: usize, index: u64) -> Error {
+    err.with_type_name(core::any::type_name::<T>())
+        .with_index(index as usize)
+        .with_offset(offset)
+}
+
+/// Returns serialized size of a collection with given elements
+#[inline]
+fn seq_size<'a, T, I>(iter: I) -> usize
+where
+    T: DesseDynamic + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    <u64>::SIZE + iter.map(DesseDynamic::serialized_size).sum::<usize>()
+}
+
+/// Returns serialized size of a map with given entries
+#[inline]
+fn map_size<'a, K, V, I>(iter: I) -> usize
+where
+    K: DesseDynamic + 'a,
+    V: DesseDynamic + 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+    <u64>::SIZE
+        + iter
+            .map(|(key, value)| key.serialized_size() + value.serialized_size())
+            .sum::<usize>()
+}
+
+/// Serializes length prefix followed by given elements
+#[inline]
+fn serialize_seq<'a, T, I, W>(len: usize, iter: I, mut writer: W, unchecked: bool) -> Result<()>
+where
+    T: DesseDynamic + 'a,
+    I: Iterator<Item = &'a T>,
+    W: Writer,
+{
+    let len = len as u64;
+
+    if unchecked {
+        DesseDynamic::serialize_into_unchecked(&len, &mut writer)?;
+
+        for item in iter {
+            DesseDynamic::serialize_into_unchecked(item, writer.inner_mut())?;
+        }
+    } else {
+        DesseDynamic::serialize_into(&len, &mut writer)?;
+
+        for item in iter {
+            DesseDynamic::serialize_into(item, writer.inner_mut())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Serializes length prefix followed by given entries (each key followed by its value)
+#[inline]
+fn serialize_map<'a, K, V, I, W>(len: usize, iter: I, mut writer: W, unchecked: bool) -> Result<()>
+where
+    K: DesseDynamic + 'a,
+    V: DesseDynamic + 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+    W: Writer,
+{
+    let len = len as u64;
+
+    if unchecked {
+        DesseDynamic::serialize_into_unchecked(&len, &mut writer)?;
+
+        for (key, value) in iter {
+            DesseDynamic::serialize_into_unchecked(key, writer.inner_mut())?;
+            DesseDynamic::serialize_into_unchecked(value, writer.inner_mut())?;
+        }
+    } else {
+        DesseDynamic::serialize_into(&len, &mut writer)?;
+
+        for (key, value) in iter {
+            DesseDynamic::serialize_into(key, writer.inner_mut())?;
+            DesseDynamic::serialize_into(value, writer.inner_mut())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads length prefix of a collection and checks it against limits of `reader`
+#[inline]
+fn read_len<R: Reader>(mut reader: R, unchecked: bool) -> Result<u64> {
+    let len = if unchecked {
+        <u64 as DesseOwned>::deserialize_owned_unchecked(&mut reader)?
+    } else {
+        <u64 as DesseOwned>::deserialize_owned(&mut reader)?
+    };
+
+    reader.check_sequence_len(len)?;
+
+    Ok(len)
+}
+
+/// Checks that `reader` has enough bytes for `len` remaining entries of at least `min_size` bytes each (skipped for
+/// unchecked deserialization)
+#[inline]
+fn check_remaining<R: Reader>(reader: R, len: u64, min_size: usize, unchecked: bool) -> Result<()> {
+    if unchecked {
+        return Ok(());
+    }
+
+    ensure_remaining(reader, len, min_size)
+}
+
+/// Deserializes a single value (checked or unchecked)
+#[inline]
+fn deserialize_value<T: DesseOwned, R: Reader>(reader: R, unchecked: bool) -> Result<T::Output> {
+    if unchecked {
+        T::deserialize_owned_unchecked(reader)
+    } else {
+        T::deserialize_owned(reader)
+    }
+}
+
+/// Deserializes a length prefixed sequence of `T`, inserting elements in collection created by `new` (with initial
+/// capacity)
+///
+/// Offset of an element (used for error context) is counted from serialized sizes of all the elements read before it,
+/// including those which did not add a new entry to collection (e.g., duplicates in a set).
+#[inline]
+fn deserialize_seq<T, R, C, N, F>(
+    mut reader: R,
+    unchecked: bool,
+    new: N,
+    mut insert: F,
+) -> Result<C>
+where
+    T: DesseOwned,
+    R: Reader,
+    N: FnOnce(usize) -> C,
+    F: FnMut(&mut C, T::Output),
+{
+    let len = read_len(&mut reader, unchecked)?;
+
+    reader.enter_nested()?;
+
+    let mut output = new(initial_capacity::<T::Output>(len));
+    let mut offset = <u64>::SIZE;
+
+    for i in 0..len {
+        let element = check_remaining(&mut reader, len - i, T::MIN_SERIALIZED_SIZE, unchecked)
+            .and_then(|()| deserialize_value::<T, _>(reader.inner_mut(), unchecked));
+
+        match element {
+            Ok(element) => {
+                offset += element.serialized_size();
+                insert(&mut output, element);
+            }
+            Err(err) => {
+                reader.exit_nested();
+                return Err(entry_error::<T>(err, offset, i));
+            }
+        }
+    }
+
+    reader.exit_nested();
+
+    Ok(output)
+}
+
+/// Deserializes a length prefixed sequence of entries of `K` and `V`, inserting them in map created by `new` (with
+/// initial capacity)
+///
+/// Offset of an entry (used for error context) is counted from serialized sizes of all the entries read before it,
+/// including those which replaced an earlier entry with same key.
+#[inline]
+fn deserialize_map<K, V, R, C, N, F>(
+    mut reader: R,
+    unchecked: bool,
+    new: N,
+    mut insert: F,
+) -> Result<C>
+where
+    K: DesseOwned,
+    V: DesseOwned,
+    R: Reader,
+    N: FnOnce(usize) -> C,
+    F: FnMut(&mut C, K::Output, V::Output),
+{
+    let min_size = K::MIN_SERIALIZED_SIZE.saturating_add(V::MIN_SERIALIZED_SIZE);
+    let len = read_len(&mut reader, unchecked)?;
+
+    reader.enter_nested()?;
+
+    let mut output = new(initial_capacity::<(K::Output, V::Output)>(len));
+    let mut offset = <u64>::SIZE;
+
+    for i in 0..len {
+        let entry = check_remaining(&mut reader, len - i, min_size, unchecked)
+            .and_then(|()| deserialize_value::<K, _>(reader.inner_mut(), unchecked))
+            .map_err(|err| entry_error::<K>(err, offset, i))
+            .and_then(|key| {
+                let value_offset = offset + key.serialized_size();
+
+                deserialize_value::<V, _>(reader.inner_mut(), unchecked)
+                    .map(|value| (key, value))
+                    .map_err(|err| entry_error::<V>(err, value_offset, i))
+            });
+
+        match entry {
+            Ok((key, value)) => {
+                offset += key.serialized_size() + value.serialized_size();
+                insert(&mut output, key, value);
+            }
+            Err(err) => {
+                reader.exit_nested();
+                return Err(err);
+            }
+        }
+    }
+
+    reader.exit_nested();
+
+    Ok(output)
+}
+
+/// Deserializes a length prefixed sequence of `T` by borrowing from `reader`, inserting elements in collection created
+/// by `new` (with initial capacity)
+#[inline]
+fn deserialize_borrowed_seq<'de, T, C, N, F>(
+    reader: &mut LimitedReader<&'de [u8]>,
+    new: N,
+    mut insert: F,
+) -> Result<C>
+where
+    T: DesseBorrowed<'de>,
+    N: FnOnce(usize) -> C,
+    F: FnMut(&mut C, T),
+{
+    let start = reader.bytes_read();
+    let len = <u64 as DesseOwned>::deserialize_owned(&mut *reader)?;
+    reader.check_sequence_len(len)?;
+
+    reader.enter_nested()?;
+
+    let mut output = new(initial_capacity::<T>(len));
+
+    for i in 0..len {
+        let offset = reader.bytes_read() - start;
+
+        match T::deserialize_borrowed_from(reader) {
+            Ok(element) => insert(&mut output, element),
+            Err(err) => {
+                reader.exit_nested();
+                return Err(entry_error::<T>(err, offset, i));
+            }
+        }
+    }
+
+    reader.exit_nested();
+
+    Ok(output)
+}
+
+/// Deserializes a length prefixed sequence of entries of `K` and `V` by borrowing from `reader`, inserting them in map
+/// created by `new` (with initial capacity)
+#[inline]
+fn deserialize_borrowed_map<'de, K, V, C, N, F>(
+    reader: &mut LimitedReader<&'de [u8]>,
+    new: N,
+    mut insert: F,
+) -> Result<C>
+where
+    K: DesseBorrowed<'de>,
+    V: DesseBorrowed<'de>,
+    N: FnOnce(usize) -> C,
+    F: FnMut(&mut C, K, V),
+{
+    let start = reader.bytes_read();
+    let len = <u64 as DesseOwned>::deserialize_owned(&mut *reader)?;
+    reader.check_sequence_len(len)?;
+
+    reader.enter_nested()?;
+
+    let mut output = new(initial_capacity::<(K, V)>(len));
+
+    for i in 0..len {
+        let key_offset = reader.bytes_read() - start;
+        let entry = K::deserialize_borrowed_from(reader)
+            .map_err(|err| entry_error::<K>(err, key_offset, i))
+            .and_then(|key| {
+                let value_offset = reader.bytes_read() - start;
+
+                V::deserialize_borrowed_from(reader)
+                    .map(|value| (key, value))
+                    .map_err(|err| entry_error::<V>(err, value_offset, i))
+            });
+
+        match entry {
+            Ok((key, value)) => insert(&mut output, key, value),
+            Err(err) => {
+                reader.exit_nested();
+                return Err(err);
+            }
+        }
+    }
+
+    reader.exit_nested();
+
+    Ok(output)
+}
+
+impl<T> DesseDynamic for VecDeque<T>
+where
+    T: DesseDynamic,
+{
+    type Output = VecDeque<T::Output>;
+
+    const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        seq_size(self.iter())
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_seq(self.len(), self.iter(), writer, false)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_seq(self.len(), self.iter(), writer, true)
+    }
+}
+
+impl<T> DesseOwned for VecDeque<T>
+where
+    T: DesseOwned,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_seq::<T, _, _, _, _>(
+            reader,
+            false,
+            VecDeque::with_capacity,
+            VecDeque::push_back,
+        )
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_seq::<T, _, _, _, _>(reader, true, VecDeque::with_capacity, VecDeque::push_back)
+    }
+}
+
+impl<'de, T> DesseBorrowed<'de> for VecDeque<T>
+where
+    T: DesseBorrowed<'de>,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        deserialize_borrowed_seq(reader, VecDeque::with_capacity, VecDeque::push_back)
+    }
+}
+
+/// Elements of a `BinaryHeap` are written in arbitrary (internal) order
+impl<T> DesseDynamic for BinaryHeap<T>
+where
+    T: DesseDynamic<Output = T> + Ord,
+{
+    type Output = Self;
+
+    const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        seq_size(self.iter())
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_seq(self.len(), self.iter(), writer, false)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_seq(self.len(), self.iter(), writer, true)
+    }
+}
+
+impl<T> DesseOwned for BinaryHeap<T>
+where
+    T: DesseOwned<Output = T> + Ord,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        Vec::<T>::deserialize_owned(reader).map(BinaryHeap::from)
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        Vec::<T>::deserialize_owned_unchecked(reader).map(BinaryHeap::from)
+    }
+}
+
+impl<'de, T> DesseBorrowed<'de> for BinaryHeap<T>
+where
+    T: DesseBorrowed<'de> + Ord,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        Vec::<T>::deserialize_borrowed_from(reader).map(BinaryHeap::from)
+    }
+}
+
+impl<T> DesseDynamic for BTreeSet<T>
+where
+    T: DesseDynamic<Output = T> + Ord,
+{
+    type Output = Self;
+
+    const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        seq_size(self.iter())
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_seq(self.len(), self.iter(), writer, false)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_seq(self.len(), self.iter(), writer, true)
+    }
+}
+
+impl<T> DesseOwned for BTreeSet<T>
+where
+    T: DesseOwned<Output = T> + Ord,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_seq::<T, _, _, _, _>(
+            reader,
+            false,
+            |_| BTreeSet::new(),
+            |output, element| {
+                output.insert(element);
+            },
+        )
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_seq::<T, _, _, _, _>(
+            reader,
+            true,
+            |_| BTreeSet::new(),
+            |output, element| {
+                output.insert(element);
+            },
+        )
+    }
+}
+
+impl<'de, T> DesseBorrowed<'de> for BTreeSet<T>
+where
+    T: DesseBorrowed<'de> + Ord,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        deserialize_borrowed_seq(
+            reader,
+            |_| BTreeSet::new(),
+            |output, element| {
+                output.insert(element);
+            },
+        )
+    }
+}
+
+impl<K, V> DesseDynamic for BTreeMap<K, V>
+where
+    K: DesseDynamic<Output = K> + Ord,
+    V: DesseDynamic,
+{
+    type Output = BTreeMap<K, V::Output>;
+
+    const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        map_size(self.iter())
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_map(self.len(), self.iter(), writer, false)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_map(self.len(), self.iter(), writer, true)
+    }
+}
+
+impl<K, V> DesseOwned for BTreeMap<K, V>
+where
+    K: DesseOwned<Output = K> + Ord,
+    V: DesseOwned,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_map::<K, V, _, _, _, _>(
+            reader,
+            false,
+            |_| BTreeMap::new(),
+            |output, key, value| {
+                output.insert(key, value);
+            },
+        )
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_map::<K, V, _, _, _, _>(
+            reader,
+            true,
+            |_| BTreeMap::new(),
+            |output, key, value| {
+                output.insert(key, value);
+            },
+        )
+    }
+}
+
+impl<'de, K, V> DesseBorrowed<'de> for BTreeMap<K, V>
+where
+    K: DesseBorrowed<'de> + Ord,
+    V: DesseBorrowed<'de>,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        deserialize_borrowed_map(
+            reader,
+            |_| BTreeMap::new(),
+            |output, key, value| {
+                output.insert(key, value);
+            },
+        )
+    }
+}
+
+/// Entries of a `HashSet` are written in arbitrary (iteration) order. Use [`Sorted`](crate::Sorted) for
+/// deterministic output.
+#[cfg(feature = "std")]
+impl<T, S> DesseDynamic for HashSet<T, S>
+where
+    T: DesseDynamic<Output = T> + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Output = Self;
+
+    const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        seq_size(self.iter())
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_seq(self.len(), self.iter(), writer, false)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_seq(self.len(), self.iter(), writer, true)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> DesseOwned for HashSet<T, S>
+where
+    T: DesseOwned<Output = T> + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_seq::<T, _, _, _, _>(
+            reader,
+            false,
+            |capacity| HashSet::with_capacity_and_hasher(capacity, S::default()),
+            |output, element| {
+                output.insert(element);
+            },
+        )
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_seq::<T, _, _, _, _>(
+            reader,
+            true,
+            |capacity| HashSet::with_capacity_and_hasher(capacity, S::default()),
+            |output, element| {
+                output.insert(element);
+            },
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, T, S> DesseBorrowed<'de> for HashSet<T, S>
+where
+    T: DesseBorrowed<'de> + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        deserialize_borrowed_seq(
+            reader,
+            |capacity| HashSet::with_capacity_and_hasher(capacity, S::default()),
+            |output, element| {
+                output.insert(element);
+            },
+        )
+    }
+}
+
+/// Entries of a `HashMap` are written in arbitrary (iteration) order. Use [`Sorted`](crate::Sorted) for
+/// deterministic output.
+#[cfg(feature = "std")]
+impl<K, V, S> DesseDynamic for HashMap<K, V, S>
+where
+    K: DesseDynamic<Output = K> + Eq + Hash,
+    V: DesseDynamic,
+    S: BuildHasher + Default,
+{
+    type Output = HashMap<K, V::Output, S>;
+
+    const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        map_size(self.iter())
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_map(self.len(), self.iter(), writer, false)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        serialize_map(self.len(), self.iter(), writer, true)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> DesseOwned for HashMap<K, V, S>
+where
+    K: DesseOwned<Output = K> + Eq + Hash,
+    V: DesseOwned,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_map::<K, V, _, _, _, _>(
+            reader,
+            false,
+            |capacity| HashMap::with_capacity_and_hasher(capacity, S::default()),
+            |output, key, value| {
+                output.insert(key, value);
+            },
+        )
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        deserialize_map::<K, V, _, _, _, _>(
+            reader,
+            true,
+            |capacity| HashMap::with_capacity_and_hasher(capacity, S::default()),
+            |output, key, value| {
+                output.insert(key, value);
+            },
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, K, V, S> DesseBorrowed<'de> for HashMap<K, V, S>
+where
+    K: DesseBorrowed<'de> + Eq + Hash,
+    V: DesseBorrowed<'de>,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        deserialize_borrowed_map(
+            reader,
+            |capacity| HashMap::with_capacity_and_hasher(capacity, S::default()),
+            |output, key, value| {
+                output.insert(key, value);
+            },
+        )
+    }
+}
+
+/// Wrapper which serializes a hash collection (`HashMap` or `HashSet`) with its entries sorted (by key), so that
+/// equal collections always have equal serialized bytes
+///
+/// Encoding is same as that of wrapped collection. As `Sorted` is deserialized into itself, it can be used as type of
+/// a field (of a derived type) or of values of another collection (e.g., `Sorted<HashMap<u8, Sorted<HashSet<u8>>>>`)
+/// for deterministic output of nested collections. Sorting allocates a vector of references to entries on every
+/// serialization.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use desse::{DesseDynamic, Sorted};
+///
+/// let a: HashMap<u8, u8> = (0..32).map(|i| (i, i)).collect();
+/// let b: HashMap<u8, u8> = (0..32).rev().map(|i| (i, i)).collect();
+///
+/// let a = Sorted(a);
+/// let bytes = a.serialize().unwrap();
+///
+/// assert_eq!(bytes, Sorted(b).serialize().unwrap());
+/// assert_eq!(a, Sorted::<HashMap<u8, u8>>::deserialize_from(&*bytes).unwrap());
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Sorted<C>(pub C);
+
+#[cfg(feature = "std")]
+impl<C> Deref for Sorted<C> {
+    type Target = C;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> DerefMut for Sorted<C> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<C> From<C> for Sorted<C> {
+    #[inline]
+    fn from(collection: C) -> Self {
+        Sorted(collection)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> DesseDynamic for Sorted<HashMap<K, V, S>>
+where
+    K: DesseDynamic<Output = K> + Eq + Hash + Ord,
+    V: DesseDynamic,
+    S: BuildHasher + Default,
+{
+    type Output = Sorted<HashMap<K, V::Output, S>>;
+
+    const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        self.0.serialized_size()
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+        serialize_map(entries.len(), entries.into_iter(), writer, false)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+
+        serialize_map(entries.len(), entries.into_iter(), writer, true)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K, V, S> DesseOwned for Sorted<HashMap<K, V, S>>
+where
+    K: DesseOwned<Output = K> + Eq + Hash + Ord,
+    V: DesseOwned,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        HashMap::<K, V, S>::deserialize_owned(reader).map(Sorted)
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        HashMap::<K, V, S>::deserialize_owned_unchecked(reader).map(Sorted)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, K, V, S> DesseBorrowed<'de> for Sorted<HashMap<K, V, S>>
+where
+    K: DesseBorrowed<'de> + Eq + Hash,
+    V: DesseBorrowed<'de>,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        HashMap::<K, V, S>::deserialize_borrowed_from(reader).map(Sorted)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> DesseDynamic for Sorted<HashSet<T, S>>
+where
+    T: DesseDynamic<Output = T> + Eq + Hash + Ord,
+    S: BuildHasher + Default,
+{
+    type Output = Self;
+
+    const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        self.0.serialized_size()
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        let mut elements = self.0.iter().collect::<Vec<_>>();
+        elements.sort_unstable();
+
+        serialize_seq(elements.len(), elements.into_iter(), writer, false)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        let mut elements = self.0.iter().collect::<Vec<_>>();
+        elements.sort_unstable();
+
+        serialize_seq(elements.len(), elements.into_iter(), writer, true)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, S> DesseOwned for Sorted<HashSet<T, S>>
+where
+    T: DesseOwned<Output = T> + Eq + Hash + Ord,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        HashSet::<T, S>::deserialize_owned(reader).map(Sorted)
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        HashSet::<T, S>::deserialize_owned_unchecked(reader).map(Sorted)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, T, S> DesseBorrowed<'de> for Sorted<HashSet<T, S>>
+where
+    T: DesseBorrowed<'de> + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        HashSet::<T, S>::deserialize_borrowed_from(reader).map(Sorted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // For initializing global memory allocator
+    extern crate std;
+
+    use super::*;
+
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    use crate::{ErrorKind, Limits};
+
+    #[test]
+    fn check_vec_deque() {
+        let deque = (0..5u16).collect::<VecDeque<_>>();
+        let bytes = DesseDynamic::serialize(&deque).unwrap();
+
+        // Same encoding as `Vec`
+        assert_eq!(
+            DesseDynamic::serialize(&vec![0u16, 1, 2, 3, 4]).unwrap(),
+            bytes
+        );
+        assert_eq!(deque, VecDeque::<u16>::deserialize_exact(&bytes).unwrap());
+        assert_eq!(
+            deque,
+            VecDeque::<u16>::deserialize_borrowed(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_binary_heap() {
+        let heap = vec![3u32, 1, 4, 1, 5]
+            .into_iter()
+            .collect::<BinaryHeap<_>>();
+        let bytes = DesseDynamic::serialize(&heap).unwrap();
+
+        assert_eq!(heap.serialized_size(), bytes.len());
+        assert_eq!(
+            vec![1, 1, 3, 4, 5],
+            BinaryHeap::<u32>::deserialize_exact(&bytes)
+                .unwrap()
+                .into_sorted_vec()
+        );
+    }
+
+    #[test]
+    fn check_btree_set() {
+        let set = vec!["b", "a", "c"]
+            .into_iter()
+            .map(String::from)
+            .collect::<BTreeSet<_>>();
+        let bytes = DesseDynamic::serialize(&set).unwrap();
+
+        assert_eq!(
+            DesseDynamic::serialize(&vec!["a", "b", "c"]).unwrap(),
+            bytes
+        );
+        assert_eq!(set, BTreeSet::<String>::deserialize_exact(&bytes).unwrap());
+        assert_eq!(
+            vec!["a", "b", "c"],
+            BTreeSet::<&str>::deserialize_borrowed(&bytes)
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn check_btree_map() {
+        let mut map = BTreeMap::new();
+        map.insert(2u8, "two".to_string());
+        map.insert(1u8, "one".to_string());
+
+        let bytes = DesseDynamic::serialize(&map).unwrap();
+
+        assert_eq!(map.serialized_size(), bytes.len());
+        assert_eq!(&[2, 0, 0, 0, 0, 0, 0, 0, 1], &bytes[..9]);
+        assert_eq!(
+            map,
+            BTreeMap::<u8, String>::deserialize_exact(&bytes).unwrap()
+        );
+
+        let borrowed = BTreeMap::<u8, &str>::deserialize_borrowed(&bytes).unwrap();
+        assert_eq!(Some(&"two"), borrowed.get(&2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn check_hash_map() {
+        let map = (0..64u32)
+            .map(|i| (i.to_string(), i))
+            .collect::<HashMap<_, _>>();
+        let bytes = DesseDynamic::serialize(&map).unwrap();
+
+        assert_eq!(
+            map,
+            HashMap::<String, u32>::deserialize_exact(&bytes).unwrap()
+        );
+
+        let borrowed = HashMap::<&str, u32>::deserialize_borrowed(&bytes).unwrap();
+        assert_eq!(Some(&42), borrowed.get("42"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn check_sorted() {
+        let a = Sorted((0..64u32).collect::<HashSet<_>>());
+        let b = Sorted((0..64u32).rev().collect::<HashSet<_>>());
+
+        let bytes = DesseDynamic::serialize(&a).unwrap();
+
+        assert_eq!(bytes, DesseDynamic::serialize(&b).unwrap());
+        assert_eq!(
+            DesseDynamic::serialize(&(0..64u32).collect::<Vec<_>>()).unwrap(),
+            bytes
+        );
+        assert_eq!(
+            b,
+            Sorted::<HashSet<u32>>::deserialize_exact(&bytes).unwrap()
+        );
+        assert_eq!(b.0, HashSet::<u32>::deserialize_exact(&bytes).unwrap());
+
+        let map = a
+            .iter()
+            .map(|i| (*i, i.to_string()))
+            .collect::<HashMap<_, _>>();
+        let sorted = map.clone().into_iter().collect::<BTreeMap<_, _>>();
+
+        assert_eq!(
+            DesseDynamic::serialize(&sorted).unwrap(),
+            DesseDynamic::serialize(&Sorted(map)).unwrap()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn check_sorted_nested() {
+        let nested = |order: &[u32]| {
+            Sorted(
+                order
+                    .iter()
+                    .map(|&i| (i, Sorted(order.iter().map(|&j| i * 100 + j).collect())))
+                    .collect::<HashMap<u32, Sorted<HashSet<u32>>>>(),
+            )
+        };
+
+        let a = nested(&(0..32).collect::<Vec<_>>());
+        let b = nested(&(0..32).rev().collect::<Vec<_>>());
+        let bytes = DesseDynamic::serialize(&a).unwrap();
+
+        assert_eq!(bytes, DesseDynamic::serialize(&b).unwrap());
+        assert_eq!(
+            a,
+            Sorted::<HashMap<u32, Sorted<HashSet<u32>>>>::deserialize_exact(&bytes).unwrap()
+        );
+        assert_eq!(
+            a,
+            Sorted::<HashMap<u32, Sorted<HashSet<u32>>>>::deserialize_borrowed(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_entry_error_context() {
+        let mut map = BTreeMap::new();
+        map.insert(1u8, 'a');
+        map.insert(2u8, 'b');
+
+        let mut bytes = DesseDynamic::serialize(&map).unwrap();
+        bytes[8 + 5 + 1..].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let err = BTreeMap::<u8, char>::deserialize_from(&*bytes).unwrap_err();
+        assert_eq!(ErrorKind::InvalidChar, err.kind());
+        assert_eq!(Some(8 + 5 + 1), err.offset());
+        assert_eq!("[1]", err.path().to_string());
+
+        let err = BTreeMap::<u8, char>::deserialize_borrowed(&bytes).unwrap_err();
+        assert_eq!(Some(8 + 5 + 1), err.offset());
+        assert_eq!("[1]", err.path().to_string());
+    }
+
+    #[test]
+    fn check_entry_error_offset_after_duplicates() {
+        // Three entries with same key, last of which has an invalid value
+        let mut bytes = 3u64.to_le_bytes().to_vec();
+
+        for value in ['a', 'b', 'c'] {
+            bytes.push(1);
+            bytes.extend_from_slice(&(value as u32).to_le_bytes());
+        }
+
+        bytes[8 + 5 + 5 + 1..].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let err = BTreeMap::<u8, char>::deserialize_from(&*bytes).unwrap_err();
+        assert_eq!(ErrorKind::InvalidChar, err.kind());
+        assert_eq!(Some(8 + 5 + 5 + 1), err.offset());
+        assert_eq!("[2]", err.path().to_string());
+
+        let err = BTreeMap::<u8, char>::deserialize_borrowed(&bytes).unwrap_err();
+        assert_eq!(Some(8 + 5 + 5 + 1), err.offset());
+
+        // Three equal elements, last of which is invalid
+        let mut bytes = DesseDynamic::serialize(&vec!['a', 'a', 'a']).unwrap();
+        bytes[8 + 4 + 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let err = BTreeSet::<char>::deserialize_from(&*bytes).unwrap_err();
+        assert_eq!(ErrorKind::InvalidChar, err.kind());
+        assert_eq!(Some(8 + 4 + 4), err.offset());
+        assert_eq!("[2]", err.path().to_string());
+    }
+
+    #[test]
+    fn check_limits() {
+        let set = (0..10u8).collect::<BTreeSet<_>>();
+        let bytes = DesseDynamic::serialize(&set).unwrap();
+
+        let limits = Limits::new().with_max_sequence_len(9);
+        let err = BTreeSet::<u8>::deserialize_from_with_limits(&*bytes, &limits).unwrap_err();
+        assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+        let mut bytes = bytes;
+        bytes[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let err = BTreeMap::<u8, u8>::deserialize_from(&*bytes).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+    }
+}
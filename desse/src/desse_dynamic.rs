@@ -1,15 +1,29 @@
 #![cfg(feature = "dynamic")]
+use alloc::borrow::Cow;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::convert::{TryFrom, TryInto};
 use core::time::Duration;
 
-use crate::{DesseSized, DesseStatic, ErrorKind, Reader, Result, Writer};
+use crate::{
+    DesseSized, DesseStatic, Error, ErrorKind, LimitedReader, Limits, Reader, Result, Writer,
+};
 
 /// Any type must implement this trait for serialization and deserialization
+///
+/// Deserialization functions of this trait are available for types which also implement
+/// [`DesseOwned`](self::DesseOwned), i.e., types which can be deserialized into an owned value (`Output`).
+///
+/// This trait can be implemented for structs and enums using `DesseDynamic` derive macro, which also implements
+/// [`DesseOwned`](self::DesseOwned) (for types without a lifetime parameter) and
+/// [`DesseBorrowed`](crate::DesseBorrowed) for zero-copy deserialization.
 pub trait DesseDynamic {
     /// Type of deserialized object
     type Output: DesseDynamic;
 
+    /// Minimum number of bytes of any serialized value of this type
+    const MIN_SERIALIZED_SIZE: usize = 0;
+
     /// Returns the size of bytes after serialization
     fn serialized_size(&self) -> usize;
 
@@ -33,17 +47,122 @@ pub trait DesseDynamic {
 
     /// Deserializes byte slice into an object
     ///
+    /// Nesting depth of containers is limited to [`Limits::DEFAULT_MAX_DEPTH`](crate::Limits::DEFAULT_MAX_DEPTH) (use
+    /// `deserialize_from_with_limits()` for a different limit).
+    ///
     /// # Error
     ///
-    /// This function returns error (`ErrorKind::InvalidSliceLength`) when length of input slice is less than expected.
-    fn deserialize_from<R: Reader>(reader: R) -> Result<Self::Output>;
+    /// This function returns error (`ErrorKind::InvalidSliceLength`) when length of input slice is less than expected
+    /// and error (`ErrorKind::LimitExceeded`) when input is nested too deeply.
+    #[inline]
+    fn deserialize_from<R: Reader>(reader: R) -> Result<Self::Output>
+    where
+        Self: DesseOwned,
+    {
+        Self::deserialize_owned(LimitedReader::new(reader, Limits::DEPTH_ONLY))
+    }
 
     /// Deserializes an object
     ///
+    /// Nesting depth of containers is limited to [`Limits::DEFAULT_MAX_DEPTH`](crate::Limits::DEFAULT_MAX_DEPTH).
+    ///
     /// # Panic
     ///
     /// This function panics when length of input slice is less than expected.
-    fn deserialize_from_unchecked<R: Reader>(reader: R) -> Result<Self::Output>;
+    #[inline]
+    fn deserialize_from_unchecked<R: Reader>(reader: R) -> Result<Self::Output>
+    where
+        Self: DesseOwned,
+    {
+        Self::deserialize_owned_unchecked(LimitedReader::new(reader, Limits::DEPTH_ONLY))
+    }
+
+    /// Deserializes byte slice into an object while enforcing given [`Limits`](crate::Limits) (use this for
+    /// untrusted input)
+    ///
+    /// # Error
+    ///
+    /// Along with all the errors returned by `deserialize_from()`, this function returns error
+    /// (`ErrorKind::LimitExceeded`) when input exceeds any of the `limits`.
+    #[inline]
+    fn deserialize_from_with_limits<R: Reader>(reader: R, limits: &Limits) -> Result<Self::Output>
+    where
+        Self: DesseOwned,
+    {
+        Self::deserialize_owned(LimitedReader::new(reader, *limits))
+    }
+
+    /// Deserializes byte slice into an object which must span the whole slice
+    ///
+    /// # Error
+    ///
+    /// Along with all the errors returned by `deserialize_from()`, this function returns error
+    /// (`ErrorKind::TrailingBytes`) when any bytes are left after deserializing the object.
+    #[inline]
+    fn deserialize_exact(bytes: &[u8]) -> Result<Self::Output>
+    where
+        Self: DesseOwned,
+    {
+        let (value, remaining) = Self::deserialize_partial(bytes)?;
+
+        if !remaining.is_empty() {
+            return Err(Error::from(ErrorKind::TrailingBytes {
+                remaining: remaining.len(),
+            })
+            .with_offset(bytes.len() - remaining.len()));
+        }
+
+        Ok(value)
+    }
+
+    /// Deserializes an object from the start of byte slice and returns it along with the bytes left after it
+    #[inline]
+    fn deserialize_partial(mut bytes: &[u8]) -> Result<(Self::Output, &[u8])>
+    where
+        Self: DesseOwned,
+    {
+        let value = Self::deserialize_from(&mut bytes)?;
+        Ok((value, bytes))
+    }
+}
+
+/// Trait for [`DesseDynamic`](self::DesseDynamic) types which can be deserialized into an owned value (`Output`)
+///
+/// Functions of this trait deserialize a value without applying default limits of deserialization functions of
+/// `DesseDynamic` (e.g., `DesseDynamic::deserialize_from()`). Implementations use them for deserializing nested values,
+/// so that nesting depth is counted across all the nested values (and recursive types do not wrap their reader again at
+/// every level of nesting). Types borrowing from input (derived types with a lifetime
+/// parameter) do not implement this trait, so, deserializing them (or a collection of them) into an owned value is a
+/// compile time error. Such types are deserialized with [`DesseBorrowed`](crate::DesseBorrowed) instead.
+///
+/// ```compile_fail
+/// use desse::DesseDynamic;
+///
+/// #[derive(DesseDynamic)]
+/// struct Entry<'a> {
+///     key: &'a str,
+/// }
+///
+/// let bytes = Entry { key: "key" }.serialize().unwrap();
+/// let entry = Entry::deserialize_from(&*bytes);
+/// ```
+pub trait DesseOwned: DesseDynamic {
+    /// Deserializes an object from `reader` (see `DesseDynamic::deserialize_from()`)
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output>;
+
+    /// Deserializes an object from `reader` (see `DesseDynamic::deserialize_from_unchecked()`)
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output>;
+}
+
+/// Maximum number of bytes allocated upfront for elements of a sequence (more are allocated as elements are
+/// deserialized)
+const MAX_PREALLOCATION: usize = 64 * 1024;
+
+/// Returns initial capacity for a sequence of `len` elements of type `T`
+#[inline]
+pub(crate) fn initial_capacity<T>(len: u64) -> usize {
+    let max_len = MAX_PREALLOCATION / core::cmp::max(1, core::mem::size_of::<T>());
+    core::cmp::min(len, max_len as u64) as usize
 }
 
 macro_rules! impl_desse_dynamic_for_static {
@@ -51,6 +170,8 @@ macro_rules! impl_desse_dynamic_for_static {
         impl DesseDynamic for $type {
             type Output = Self;
 
+            const MIN_SERIALIZED_SIZE: usize = <$type>::SIZE;
+
             #[inline]
             fn serialized_size(&self) -> usize {
                 <$type>::SIZE
@@ -70,9 +191,11 @@ macro_rules! impl_desse_dynamic_for_static {
             fn serialize_into_unchecked<W: Writer>(&self, mut writer: W) -> Result<()> {
                 writer.write_unchecked(&<$type as DesseStatic>::serialize(self))
             }
+        }
 
+        impl DesseOwned for $type {
             #[inline]
-            fn deserialize_from<R: Reader>(mut reader: R) -> Result<Self::Output> {
+            fn deserialize_owned<R: Reader>(mut reader: R) -> Result<Self::Output> {
                 let bytes = reader.read(<$type>::SIZE)?;
                 unsafe {
                     <$type as DesseStatic>::deserialize_from(
@@ -82,7 +205,7 @@ macro_rules! impl_desse_dynamic_for_static {
             }
 
             #[inline]
-            fn deserialize_from_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
+            fn deserialize_owned_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
                 let bytes = reader.read_unchecked(<$type>::SIZE)?;
                 unsafe {
                     <$type as DesseStatic>::deserialize_from(
@@ -111,11 +234,59 @@ impl_desse_dynamic_for_static!(i128);
 
 impl_desse_dynamic_for_static!(Duration);
 
+/// Converts bytes of a serialized string into `String`, recording offset of invalid UTF-8 sequence in error
+#[inline]
+fn string_from_utf8(bytes: Vec<u8>) -> Result<String> {
+    String::from_utf8(bytes).map_err(|err| {
+        let offset = <u64>::SIZE + err.utf8_error().valid_up_to();
+
+        Error::from(err)
+            .with_type_name(core::any::type_name::<String>())
+            .with_offset(offset)
+    })
+}
+
+/// Checks that `reader` has enough bytes for `len` remaining elements of a sequence, each of at least `min_size` bytes
+///
+/// This is checked before every element (instead of only once after length prefix), so that readers which buffer their
+/// input ([`IncrementalDecoder`](crate::IncrementalDecoder)) learn how many bytes rest of the sequence needs at least,
+/// instead of one element at a time.
+#[inline]
+pub(crate) fn ensure_remaining<R: Reader>(mut reader: R, len: u64, min_size: usize) -> Result<()> {
+    reader.ensure(
+        usize::try_from(len)
+            .unwrap_or(usize::MAX)
+            .saturating_mul(min_size),
+    )
+}
+
+/// Adds context of element at `index` of a sequence (which starts after its `prefix_size` bytes long length prefix and
+/// the serialized elements in `output`) to `err`
+#[cold]
+fn element_error<T: DesseDynamic>(
+    err: Error,
+    prefix_size: usize,
+    output: &[T],
+    index: usize,
+) -> Error {
+    let offset = prefix_size
+        + output
+            .iter()
+            .map(DesseDynamic::serialized_size)
+            .sum::<usize>();
+
+    err.with_type_name(core::any::type_name::<T>())
+        .with_index(index)
+        .with_offset(offset)
+}
+
 macro_rules! impl_desse_dynamic_str {
     ($type: ty) => {
         impl DesseDynamic for $type {
             type Output = String;
 
+            const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
             #[inline]
             fn serialized_size(&self) -> usize {
                 <u64>::SIZE + self.len()
@@ -141,17 +312,21 @@ macro_rules! impl_desse_dynamic_str {
                 DesseDynamic::serialize_into_unchecked(&len, &mut writer)?;
                 writer.write_unchecked(&self.as_bytes())
             }
+        }
 
+        impl DesseOwned for $type {
             #[inline]
-            fn deserialize_from<R: Reader>(mut reader: R) -> Result<Self::Output> {
-                let len = <u64 as DesseDynamic>::deserialize_from(&mut reader)?;
-                Ok(String::from_utf8(reader.read(len as usize)?.to_vec())?)
+            fn deserialize_owned<R: Reader>(mut reader: R) -> Result<Self::Output> {
+                let len = <u64 as DesseOwned>::deserialize_owned(&mut reader)?;
+                reader.check_string_len(len)?;
+                string_from_utf8(reader.read(len as usize)?.to_vec())
             }
 
             #[inline]
-            fn deserialize_from_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
-                let len = <u64 as DesseDynamic>::deserialize_from_unchecked(&mut reader)?;
-                Ok(String::from_utf8(reader.read(len as usize)?.to_vec())?)
+            fn deserialize_owned_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
+                let len = <u64 as DesseOwned>::deserialize_owned_unchecked(&mut reader)?;
+                reader.check_string_len(len)?;
+                string_from_utf8(reader.read(len as usize)?.to_vec())
             }
         }
     };
@@ -159,21 +334,137 @@ macro_rules! impl_desse_dynamic_str {
 
 impl_desse_dynamic_str!(&str);
 impl_desse_dynamic_str!(String);
+impl_desse_dynamic_str!(Cow<'_, str>);
 
-impl<T> DesseDynamic for Vec<T>
+macro_rules! impl_desse_dynamic_seq {
+    ($type: ty) => {
+        impl<T> DesseDynamic for $type
+        where
+            T: DesseDynamic,
+        {
+            type Output = Vec<T::Output>;
+
+            const MIN_SERIALIZED_SIZE: usize = <u64>::SIZE;
+
+            #[inline]
+            fn serialized_size(&self) -> usize {
+                let sum = self
+                    .iter()
+                    .map(DesseDynamic::serialized_size)
+                    .sum::<usize>();
+
+                <u64>::SIZE + sum
+            }
+
+            #[inline]
+            fn serialize(&self) -> Result<Vec<u8>> {
+                let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+                DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+                Ok(bytes)
+            }
+
+            #[inline]
+            fn serialize_into<W: Writer>(&self, mut writer: W) -> Result<()> {
+                let len = self.len() as u64;
+                DesseDynamic::serialize_into(&len, &mut writer)?;
+
+                for item in self.iter() {
+                    DesseDynamic::serialize_into(item, writer.inner_mut())?;
+                }
+
+                Ok(())
+            }
+
+            #[inline]
+            fn serialize_into_unchecked<W: Writer>(&self, mut writer: W) -> Result<()> {
+                let len = self.len() as u64;
+                DesseDynamic::serialize_into_unchecked(&len, &mut writer)?;
+
+                for item in self.iter() {
+                    DesseDynamic::serialize_into_unchecked(item, writer.inner_mut())?;
+                }
+
+                Ok(())
+            }
+        }
+
+        impl<T> DesseOwned for $type
+        where
+            T: DesseOwned,
+        {
+            #[inline]
+            fn deserialize_owned<R: Reader>(mut reader: R) -> Result<Self::Output> {
+                let len = <u64 as DesseOwned>::deserialize_owned(&mut reader)?;
+                reader.check_sequence_len(len)?;
+
+                reader.enter_nested()?;
+
+                let mut output = Vec::with_capacity(initial_capacity::<T::Output>(len));
+
+                let mut i = 0;
+                while i < len {
+                    let element = ensure_remaining(&mut reader, len - i, T::MIN_SERIALIZED_SIZE)
+                        .and_then(|()| <T as DesseOwned>::deserialize_owned(reader.inner_mut()));
+
+                    match element {
+                        Ok(element) => output.push(element),
+                        Err(err) => {
+                            reader.exit_nested();
+                            return Err(element_error(err, <u64>::SIZE, &output, i as usize));
+                        }
+                    }
+                    i += 1;
+                }
+
+                reader.exit_nested();
+
+                Ok(output)
+            }
+
+            #[inline]
+            fn deserialize_owned_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
+                let len = <u64 as DesseOwned>::deserialize_owned_unchecked(&mut reader)?;
+                reader.check_sequence_len(len)?;
+
+                reader.enter_nested()?;
+
+                let mut output = Vec::with_capacity(initial_capacity::<T::Output>(len));
+
+                let mut i = 0;
+                while i < len {
+                    match <T as DesseOwned>::deserialize_owned_unchecked(reader.inner_mut()) {
+                        Ok(element) => output.push(element),
+                        Err(err) => {
+                            reader.exit_nested();
+                            return Err(element_error(err, <u64>::SIZE, &output, i as usize));
+                        }
+                    }
+                    i += 1;
+                }
+
+                reader.exit_nested();
+
+                Ok(output)
+            }
+        }
+    };
+}
+
+impl_desse_dynamic_seq!(Vec<T>);
+impl_desse_dynamic_seq!(&[T]);
+
+/// Arrays are serialized without a length prefix
+impl<T, const N: usize> DesseDynamic for [T; N]
 where
     T: DesseDynamic,
 {
-    type Output = Vec<T::Output>;
+    type Output = [T::Output; N];
+
+    const MIN_SERIALIZED_SIZE: usize = T::MIN_SERIALIZED_SIZE.saturating_mul(N);
 
     #[inline]
     fn serialized_size(&self) -> usize {
-        let sum = self
-            .iter()
-            .map(DesseDynamic::serialized_size)
-            .sum::<usize>();
-
-        <u64>::SIZE + sum
+        self.iter().map(DesseDynamic::serialized_size).sum()
     }
 
     #[inline]
@@ -185,11 +476,8 @@ where
 
     #[inline]
     fn serialize_into<W: Writer>(&self, mut writer: W) -> Result<()> {
-        let len = self.len() as u64;
-        DesseDynamic::serialize_into(&len, &mut writer)?;
-
         for item in self.iter() {
-            DesseDynamic::serialize_into(item, &mut writer)?;
+            DesseDynamic::serialize_into(item, writer.inner_mut())?;
         }
 
         Ok(())
@@ -197,53 +485,92 @@ where
 
     #[inline]
     fn serialize_into_unchecked<W: Writer>(&self, mut writer: W) -> Result<()> {
-        let len = self.len() as u64;
-        DesseDynamic::serialize_into_unchecked(&len, &mut writer)?;
-
         for item in self.iter() {
-            DesseDynamic::serialize_into_unchecked(item, &mut writer)?;
+            DesseDynamic::serialize_into_unchecked(item, writer.inner_mut())?;
         }
 
         Ok(())
     }
+}
 
+impl<T, const N: usize> DesseOwned for [T; N]
+where
+    T: DesseOwned,
+{
     #[inline]
-    fn deserialize_from<R: Reader>(mut reader: R) -> Result<Self::Output> {
-        let len = <u64 as DesseDynamic>::deserialize_from(&mut reader)?;
-        let mut output = Vec::with_capacity(len as usize);
-
-        let mut i = 0;
-        while i < len {
-            output.push(<T as DesseDynamic>::deserialize_from(&mut reader)?);
-            i += 1;
+    fn deserialize_owned<R: Reader>(mut reader: R) -> Result<Self::Output> {
+        reader.ensure(Self::MIN_SERIALIZED_SIZE)?;
+        reader.enter_nested()?;
+
+        let mut output = Vec::with_capacity(N);
+
+        for i in 0..N {
+            match <T as DesseOwned>::deserialize_owned(reader.inner_mut()) {
+                Ok(element) => output.push(element),
+                Err(err) => {
+                    reader.exit_nested();
+                    return Err(element_error(err, 0, &output, i));
+                }
+            }
         }
 
-        Ok(output)
+        reader.exit_nested();
+
+        Ok(array_from_vec(output))
     }
 
     #[inline]
-    fn deserialize_from_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
-        let len = <u64 as DesseDynamic>::deserialize_from_unchecked(&mut reader)?;
-        let mut output = Vec::with_capacity(len as usize);
-
-        let mut i = 0;
-        while i < len {
-            output.push(<T as DesseDynamic>::deserialize_from_unchecked(
-                &mut reader,
-            )?);
-            i += 1;
+    fn deserialize_owned_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
+        reader.enter_nested()?;
+
+        let mut output = Vec::with_capacity(N);
+
+        for i in 0..N {
+            match <T as DesseOwned>::deserialize_owned_unchecked(reader.inner_mut()) {
+                Ok(element) => output.push(element),
+                Err(err) => {
+                    reader.exit_nested();
+                    return Err(element_error(err, 0, &output, i));
+                }
+            }
         }
 
-        Ok(output)
+        reader.exit_nested();
+
+        Ok(array_from_vec(output))
+    }
+}
+
+/// Converts a vector of exactly `N` elements into an array
+#[inline]
+pub(crate) fn array_from_vec<T, const N: usize>(elements: Vec<T>) -> [T; N] {
+    match elements.try_into() {
+        Ok(array) => array,
+        Err(_) => unreachable!("Vector should have exactly `N` elements"),
     }
 }
 
+/// Adds context of value of `Some`, `Ok` or `Err` (which starts after tag byte) to `err`
+#[cold]
+fn tagged_value_error<T: DesseDynamic>(err: Error) -> Error {
+    err.with_type_name(core::any::type_name::<T::Output>())
+        .with_offset(<u8>::SIZE)
+}
+
+/// Returns error for an invalid tag byte of `Option`
+#[cold]
+fn invalid_option_tag<T: DesseDynamic>() -> Error {
+    Error::from(ErrorKind::InvalidInput).with_type_name(core::any::type_name::<Option<T::Output>>())
+}
+
 impl<T> DesseDynamic for Option<T>
 where
     T: DesseDynamic,
 {
     type Output = Option<T::Output>;
 
+    const MIN_SERIALIZED_SIZE: usize = <u8>::SIZE;
+
     #[inline]
     fn serialized_size(&self) -> usize {
         let inner_size = self
@@ -282,32 +609,171 @@ where
             }
         }
     }
+}
 
+impl<T> DesseOwned for Option<T>
+where
+    T: DesseOwned,
+{
     #[inline]
-    fn deserialize_from<R: Reader>(mut reader: R) -> Result<Self::Output> {
-        let option = <u8 as DesseDynamic>::deserialize_from(&mut reader)?;
+    fn deserialize_owned<R: Reader>(mut reader: R) -> Result<Self::Output> {
+        let option = <u8 as DesseOwned>::deserialize_owned(&mut reader)?;
 
         match option {
             0 => Ok(None),
-            1 => Ok(Some(<T as DesseDynamic>::deserialize_from(reader)?)),
-            _ => Err(ErrorKind::InvalidInput.into()),
+            1 => {
+                reader.enter_nested()?;
+                let value = <T as DesseOwned>::deserialize_owned(reader.inner_mut());
+                reader.exit_nested();
+
+                value.map(Some).map_err(|err| tagged_value_error::<T>(err))
+            }
+            _ => Err(invalid_option_tag::<T>()),
         }
     }
 
     #[inline]
-    fn deserialize_from_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
-        let option = <u8 as DesseDynamic>::deserialize_from_unchecked(&mut reader)?;
+    fn deserialize_owned_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
+        let option = <u8 as DesseOwned>::deserialize_owned_unchecked(&mut reader)?;
 
         match option {
             0 => Ok(None),
-            1 => Ok(Some(<T as DesseDynamic>::deserialize_from_unchecked(
-                reader,
-            )?)),
-            _ => Err(ErrorKind::InvalidInput.into()),
+            1 => {
+                reader.enter_nested()?;
+                let value = <T as DesseOwned>::deserialize_owned_unchecked(reader.inner_mut());
+                reader.exit_nested();
+
+                value.map(Some).map_err(|err| tagged_value_error::<T>(err))
+            }
+            _ => Err(invalid_option_tag::<T>()),
+        }
+    }
+}
+
+/// Returns error for an invalid tag byte of `Result`
+#[cold]
+fn invalid_result_tag<T: DesseDynamic, E: DesseDynamic>() -> Error {
+    Error::from(ErrorKind::InvalidTag)
+        .with_type_name(core::any::type_name::<
+            core::result::Result<T::Output, E::Output>,
+        >())
+        .with_offset(0)
+}
+
+/// `Result` is serialized as a tag byte (`0` for `Ok` and `1` for `Err`) followed by the value of its variant
+impl<T, E> DesseDynamic for core::result::Result<T, E>
+where
+    T: DesseDynamic,
+    E: DesseDynamic,
+{
+    type Output = core::result::Result<T::Output, E::Output>;
+
+    // Only tag byte is counted (like `Option`), so that a recursive type (e.g., a struct with a field of type
+    // `Result<u8, Box<Self>>`) does not depend on its own `MIN_SERIALIZED_SIZE`
+    const MIN_SERIALIZED_SIZE: usize = <u8>::SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        let inner_size = match self {
+            Ok(ref value) => DesseDynamic::serialized_size(value),
+            Err(ref err) => DesseDynamic::serialized_size(err),
+        };
+
+        inner_size + <u8>::SIZE
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(DesseDynamic::serialized_size(self));
+        DesseDynamic::serialize_into_unchecked(self, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, mut writer: W) -> Result<()> {
+        match self {
+            Ok(ref value) => {
+                DesseDynamic::serialize_into(&0u8, &mut writer)?;
+                DesseDynamic::serialize_into(value, writer)
+            }
+            Err(ref err) => {
+                DesseDynamic::serialize_into(&1u8, &mut writer)?;
+                DesseDynamic::serialize_into(err, writer)
+            }
+        }
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, mut writer: W) -> Result<()> {
+        match self {
+            Ok(ref value) => {
+                DesseDynamic::serialize_into_unchecked(&0u8, &mut writer)?;
+                DesseDynamic::serialize_into_unchecked(value, writer)
+            }
+            Err(ref err) => {
+                DesseDynamic::serialize_into_unchecked(&1u8, &mut writer)?;
+                DesseDynamic::serialize_into_unchecked(err, writer)
+            }
         }
     }
 }
 
+impl<T, E> DesseOwned for core::result::Result<T, E>
+where
+    T: DesseOwned,
+    E: DesseOwned,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(mut reader: R) -> Result<Self::Output> {
+        let tag = <u8 as DesseOwned>::deserialize_owned(&mut reader)?;
+
+        if tag > 1 {
+            return Err(invalid_result_tag::<T, E>());
+        }
+
+        reader.enter_nested()?;
+
+        let value = if tag == 0 {
+            <T as DesseOwned>::deserialize_owned(reader.inner_mut())
+                .map(Ok)
+                .map_err(tagged_value_error::<T>)
+        } else {
+            <E as DesseOwned>::deserialize_owned(reader.inner_mut())
+                .map(Err)
+                .map_err(tagged_value_error::<E>)
+        };
+
+        reader.exit_nested();
+
+        value
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
+        let tag = <u8 as DesseOwned>::deserialize_owned_unchecked(&mut reader)?;
+
+        if tag > 1 {
+            return Err(invalid_result_tag::<T, E>());
+        }
+
+        reader.enter_nested()?;
+
+        let value = if tag == 0 {
+            <T as DesseOwned>::deserialize_owned_unchecked(reader.inner_mut())
+                .map(Ok)
+                .map_err(tagged_value_error::<T>)
+        } else {
+            <E as DesseOwned>::deserialize_owned_unchecked(reader.inner_mut())
+                .map(Err)
+                .map_err(tagged_value_error::<E>)
+        };
+
+        reader.exit_nested();
+
+        value
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // For initializing global memory allocator
@@ -318,6 +784,8 @@ mod tests {
     use alloc::string::ToString;
     use alloc::vec;
 
+    use crate::DesseBorrowed;
+
     macro_rules! impl_desse_dynamic_test {
         ($type: ty, $name: ident) => {
             #[test]
@@ -380,6 +848,77 @@ mod tests {
         assert_eq!(v, new_v, "Invalid serialization / deserialization")
     }
 
+    #[test]
+    fn check_deserialize_exact() {
+        let bytes = DesseDynamic::serialize(&"hello").unwrap();
+        assert_eq!("hello", String::deserialize_exact(&bytes).unwrap());
+
+        let mut bytes = DesseDynamic::serialize(&vec![1u16, 2]).unwrap();
+        bytes.extend_from_slice(&[7, 8, 9]);
+
+        let err = Vec::<u16>::deserialize_exact(&bytes).unwrap_err();
+        assert_eq!(ErrorKind::TrailingBytes { remaining: 3 }, err.kind());
+        assert_eq!(Some(12), err.offset());
+
+        let err = Vec::<u16>::deserialize_exact(&bytes[..11]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+    }
+
+    #[test]
+    fn check_deserialize_partial() {
+        let mut bytes = DesseDynamic::serialize(&Some(5u32)).unwrap();
+        bytes.extend_from_slice(&[7, 8]);
+
+        let (value, remaining) = Option::<u32>::deserialize_partial(&bytes).unwrap();
+        assert_eq!(Some(5), value);
+        assert_eq!(&[7, 8], remaining);
+
+        let (value, remaining) = u16::deserialize_partial(remaining).unwrap();
+        assert_eq!(0x0807, value);
+        assert!(remaining.is_empty());
+    }
+
+    #[test]
+    fn check_array() {
+        let array = ["a".to_string(), "bc".to_string()];
+        let bytes = DesseDynamic::serialize(&array).unwrap();
+
+        assert_eq!(9 + 10, bytes.len());
+        assert_eq!(16, <[String; 2]>::MIN_SERIALIZED_SIZE);
+        assert_eq!(array, <[String; 2]>::deserialize_exact(&bytes).unwrap());
+        assert_eq!(
+            ["a", "bc"],
+            <[&str; 2]>::deserialize_borrowed(&bytes).unwrap()
+        );
+
+        // Static elements have same encoding as `DesseStatic`
+        assert_eq!(
+            &[1, 0, 2, 0],
+            &DesseDynamic::serialize(&[1u16, 2]).unwrap()[..]
+        );
+
+        let mut bytes = bytes;
+        bytes[9 + 8] = 0xFF;
+
+        let err = <[String; 2]>::deserialize_from(&*bytes).unwrap_err();
+        assert_eq!(ErrorKind::InvalidStr, err.kind());
+        assert_eq!(Some(9 + 8), err.offset());
+        assert_eq!("[1]", err.path().to_string());
+
+        let err = <[&str; 2]>::deserialize_borrowed(&bytes).unwrap_err();
+        assert_eq!(Some(9 + 8), err.offset());
+        assert_eq!("[1]", err.path().to_string());
+    }
+
+    #[test]
+    fn check_vec_invalid_length() {
+        let mut serialized = DesseDynamic::serialize(&vec![1u32, 2]).unwrap();
+        serialized[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let err = Vec::<u32>::deserialize_from(&*serialized).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+    }
+
     #[test]
     fn check_option_none() {
         let option: Option<String> = None;
@@ -401,4 +940,40 @@ mod tests {
             "Invalid serialization / deserialization"
         );
     }
+
+    #[test]
+    fn check_result() {
+        for result in [Ok(7u16), Err("failed".to_string())] {
+            let serialized = DesseDynamic::serialize(&result).unwrap();
+            assert_eq!(DesseDynamic::serialized_size(&result), serialized.len());
+
+            let new_result =
+                core::result::Result::<u16, String>::deserialize_exact(&serialized).unwrap();
+            assert_eq!(
+                result, new_result,
+                "Invalid serialization / deserialization"
+            );
+        }
+
+        assert_eq!(
+            &[0, 7, 0],
+            &DesseDynamic::serialize(&Ok::<u16, u8>(7)).unwrap()[..]
+        );
+        assert_eq!(1, <core::result::Result<u16, String>>::MIN_SERIALIZED_SIZE);
+    }
+
+    #[test]
+    fn check_result_errors() {
+        let err = core::result::Result::<u16, u8>::deserialize_from(&[2u8, 0, 0][..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidTag, err.kind());
+        assert_eq!(Some(0), err.offset());
+
+        let err = core::result::Result::<u16, String>::deserialize_from(&[1u8, 3][..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+        assert_eq!(Some(1), err.offset());
+
+        let err =
+            core::result::Result::<u16, String>::deserialize_borrowed(&[3u8][..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidTag, err.kind());
+    }
 }
@@ -1,6 +1,9 @@
 use core::time::Duration;
 
-use crate::{ErrorKind, Result};
+use crate::{Error, ErrorKind, Result};
+
+/// Number of nanoseconds in a second
+const NANOS_PER_SEC: u32 = 1_000_000_000;
 
 /// Any type must implement this trait for serialization and deserialization
 pub trait DesseStatic: Sized {
@@ -15,6 +18,23 @@ pub trait DesseStatic: Sized {
 
     /// Deserializes an object
     fn deserialize_from(bytes: &Self::Output) -> Result<Self>;
+
+    /// Deserializes an object in strict mode, which accepts only the canonical encoding of a value (i.e., the bytes
+    /// returned by `serialize()`)
+    ///
+    /// # Error
+    ///
+    /// Along with all the errors returned by `deserialize_from()`, this function returns error:
+    ///
+    /// - (`ErrorKind::NonZeroReserved`) when any of the reserved bytes (`#[desse(reserved = N)]`) is non-zero.
+    /// - (`ErrorKind::InvalidBool`) when byte of a `bool` is neither `0` nor `1`.
+    /// - (`ErrorKind::NonZeroPadding`) when any of the padding bytes after a variant shorter than its enum is non-zero.
+    /// - (`ErrorKind::NonZeroUnusedBits`) when any of the bits not used by a value's encoding is non-zero (e.g.,
+    ///   nanoseconds of a `Duration` are not less than `1_000_000_000`).
+    #[inline]
+    fn deserialize_from_strict(bytes: &Self::Output) -> Result<Self> {
+        Self::deserialize_from(bytes)
+    }
 }
 
 /// Helper trait used to compute `SIZE` of a type at compile time
@@ -23,6 +43,85 @@ pub trait DesseSized {
     const SIZE: usize;
 }
 
+/// Trait for types with a versioned layout (`#[desse(version = N)]`) which can also be deserialized from bytes written
+/// with an earlier layout (`#[desse(previous = "OldType")]`)
+///
+/// First byte of a versioned type's serialized bytes contains its layout `VERSION`. While deserializing, if this byte
+/// does not match current `VERSION`, deserialization is delegated to `previous` type and result is converted using
+/// `From`.
+///
+/// # Migrating stored records
+///
+/// [`deserialize_versioned()`](Self::deserialize_versioned) is the migration path for stored records: it takes a
+/// byte slice of any length and reads only as many bytes as the layout selected by version byte needs.
+/// `DesseStatic::deserialize_from()` takes an array of current `SIZE`, so, it can only read a record with an older
+/// (shorter) layout after it is zero-padded to current `SIZE`.
+///
+/// Records written before a type was versioned have no version byte. They can be migrated by implementing this trait
+/// manually for the old type and using it as `previous`. Note that such records are selected only when their first
+/// byte does not match any of the newer versions, so, this is reliable only when that byte is known to never match
+/// (e.g., when it always holds a small field value).
+///
+/// ```
+/// use desse::{DesseSized, DesseStatic, DesseVersioned, ErrorKind, Result};
+///
+/// // Layout written before versioning was introduced
+/// #[derive(Debug, DesseSized, DesseStatic)]
+/// struct Legacy {
+///     kind: u8,
+///     id: u32,
+/// }
+///
+/// impl DesseVersioned for Legacy {
+///     const VERSION: u8 = 0;
+///
+///     fn deserialize_versioned(bytes: &[u8]) -> Result<Self> {
+///         if bytes.len() < Self::SIZE {
+///             return Err(ErrorKind::InvalidSliceLength.into());
+///         }
+///
+///         let mut array = [0; Self::SIZE];
+///         array.copy_from_slice(&bytes[..Self::SIZE]);
+///         Self::deserialize_from(&array)
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+/// #[desse(version = 200, previous = "Legacy")]
+/// struct Record {
+///     kind: u8,
+///     id: u32,
+///     flags: u16,
+/// }
+///
+/// impl From<Legacy> for Record {
+///     fn from(old: Legacy) -> Self {
+///         Self { kind: old.kind, id: old.id, flags: 0 }
+///     }
+/// }
+///
+/// let old = Legacy { kind: 1, id: 42 }.serialize();
+///
+/// assert_eq!(
+///     Record { kind: 1, id: 42, flags: 0 },
+///     Record::deserialize_versioned(&old).unwrap()
+/// );
+/// ```
+pub trait DesseVersioned: DesseStatic + DesseSized {
+    /// Layout version written in first byte of serialized object
+    const VERSION: u8;
+
+    /// Deserializes an object from bytes written with current or any earlier layout (`bytes` may be longer than the
+    /// selected layout, trailing bytes are ignored)
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::UnknownVersion`) when none of the known layouts have the version
+    /// stored in first byte and (`ErrorKind::InvalidSliceLength`) when length of input slice is less than the size of
+    /// selected layout.
+    fn deserialize_versioned(bytes: &[u8]) -> Result<Self>;
+}
+
 macro_rules! impl_desse_static {
     ($type: ty) => {
         impl DesseSized for $type {
@@ -147,12 +246,22 @@ macro_rules! impl_desse_static_arr_char {
                 let mut counter = 0;
 
                 for i in 0..$num {
-                    unsafe {
-                        arr[i] = <char>::deserialize_from(
+                    let element = unsafe {
+                        <char>::deserialize_from(
                             &*(bytes[counter..(counter + <char>::SIZE)].as_ptr()
                                 as *const [u8; <char>::SIZE]),
-                        )?;
-                    }
+                        )
+                    };
+
+                    arr[i] = match element {
+                        Ok(element) => element,
+                        Err(err) => {
+                            return Err(err
+                                .with_type_name("char")
+                                .with_index(i)
+                                .with_offset(counter))
+                        }
+                    };
 
                     counter += <char>::SIZE;
                 }
@@ -210,6 +319,22 @@ macro_rules! impl_desse_static_arr_bool {
 
                 Ok(arr)
             }
+
+            #[inline]
+            fn deserialize_from_strict(bytes: &Self::Output) -> Result<Self> {
+                let mut arr: Self = [false; $num];
+
+                for (i, byte) in bytes.iter().enumerate() {
+                    arr[i] = match <bool>::deserialize_from_strict(&[*byte]) {
+                        Ok(element) => element,
+                        Err(err) => {
+                            return Err(err.with_type_name("bool").with_index(i).with_offset(i))
+                        }
+                    };
+                }
+
+                Ok(arr)
+            }
         }
     };
 }
@@ -247,6 +372,15 @@ impl DesseStatic for bool {
     fn deserialize_from(bytes: &Self::Output) -> Result<Self> {
         Ok(u8::from_le_bytes(*bytes) != 0)
     }
+
+    #[inline]
+    fn deserialize_from_strict(bytes: &Self::Output) -> Result<Self> {
+        match u8::from_le_bytes(*bytes) {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ErrorKind::InvalidBool.into()),
+        }
+    }
 }
 
 impl DesseSized for char {
@@ -316,6 +450,56 @@ impl DesseStatic for Duration {
             ))
         }
     }
+
+    #[inline]
+    fn deserialize_from_strict(bytes: &Self::Output) -> Result<Self> {
+        let nanos = unsafe {
+            <u32>::deserialize_from(
+                &*(bytes[<u64>::SIZE..(<u64>::SIZE + <u32>::SIZE)].as_ptr()
+                    as *const [u8; <u32>::SIZE]),
+            )?
+        };
+
+        // Nanoseconds beyond a second are carried into seconds by `Duration::new()`, so they are not canonical
+        if nanos >= NANOS_PER_SEC {
+            return Err(Error::from(ErrorKind::NonZeroUnusedBits)
+                .with_type_name("core::time::Duration")
+                .with_offset(<u64>::SIZE));
+        }
+
+        Self::deserialize_from(bytes)
+    }
+}
+
+/// Size of a `Result` is a tag byte followed by the size of its largest variant.
+///
+/// Note that `DesseStatic` is not implemented for `Result` because length of its output array would depend on generic
+/// parameters, which is not supported on stable Rust. Instead, `DesseStatic` derive macro serializes fields of type
+/// `Result<T, E>` (where both `T` and `E` implement `DesseStatic`) itself: a tag byte (`0` for `Ok` and `1` for `Err`)
+/// followed by the value, padded with zeros to the size of larger value. Use `DesseDynamic` for serializing `Result`
+/// values outside of a derived type.
+///
+/// ```
+/// use desse::{DesseSized, DesseStatic};
+///
+/// #[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+/// struct Response {
+///     id: u16,
+///     result: Result<u32, u8>,
+/// }
+///
+/// let response = Response { id: 1, result: Err(7) };
+/// let bytes = response.serialize();
+///
+/// assert_eq!([1, 0, 1, 7, 0, 0, 0], bytes);
+/// assert_eq!(response, Response::deserialize_from(&bytes).unwrap());
+/// ```
+impl<T, E> DesseSized for core::result::Result<T, E>
+where
+    T: DesseSized,
+    E: DesseSized,
+{
+    const SIZE: usize = <u8>::SIZE + if T::SIZE > E::SIZE { T::SIZE } else { E::SIZE };
 }
 
 impl_desse_size_static_arr!(1);
@@ -1183,4 +1367,10 @@ mod tests {
     impl_desse_static_test!([i128; 30], check_arr_i128_30);
     impl_desse_static_test!([i128; 31], check_arr_i128_31);
     impl_desse_static_test!([i128; 32], check_arr_i128_32);
+
+    #[test]
+    fn check_result_size() {
+        assert_eq!(1 + 8, <core::result::Result<u64, u16>>::SIZE);
+        assert_eq!(1 + 12, <core::result::Result<u8, Duration>>::SIZE);
+    }
 }
@@ -1,15 +1,46 @@
 use core::fmt::{Display, Formatter};
+use core::iter::Rev;
+use core::slice::Iter;
+#[cfg(feature = "dynamic")]
+use core::str::Utf8Error;
 
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 #[cfg(feature = "dynamic")]
 use alloc::string::FromUtf8Error;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Alias of `Result` objects that return [`Error`](self::Error)
 pub type Result<T> = core::result::Result<T, Error>;
 
 /// An opaque error type, used for all errors in this crate
+///
+/// Along with its [`ErrorKind`](self::ErrorKind), an error carries context filled in by derived implementations and
+/// containers while it propagates: byte offset in input, name of the type being decoded and path of the field which
+/// failed (e.g., `Order.lines[3].sku`).
+///
+/// Context is allocated only when an error occurs, so it costs nothing when deserialization succeeds. It is recorded
+/// only when `alloc` feature (enabled by `dynamic`) is enabled; otherwise, all the context accessors return empty
+/// values.
 #[derive(Debug)]
 pub struct Error {
     kind: ErrorKind,
+    #[cfg(feature = "alloc")]
+    context: Option<Box<Context>>,
+}
+
+/// Context of an error, collected while it propagates
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+struct Context {
+    offset: usize,
+    type_name: Option<&'static str>,
+    root: Option<&'static str>,
+    /// Segments of path, from innermost to outermost (so that prepending a segment is a push)
+    path: Vec<PathSegment>,
+    #[cfg(feature = "std")]
+    source: Option<Box<dyn std::error::Error + Send + Sync>>,
 }
 
 impl Error {
@@ -18,24 +49,237 @@ impl Error {
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
+
+    /// Returns byte offset in input (relative to start of outermost value that recorded context) at which
+    /// deserialization failed, if recorded
+    #[inline]
+    pub fn offset(&self) -> Option<usize> {
+        #[cfg(feature = "alloc")]
+        {
+            self.context.as_ref().map(|context| context.offset)
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            None
+        }
+    }
+
+    /// Returns name of the innermost type being decoded when deserialization failed, if recorded
+    #[inline]
+    pub fn type_name(&self) -> Option<&'static str> {
+        #[cfg(feature = "alloc")]
+        {
+            self.context.as_ref().and_then(|context| context.type_name)
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            None
+        }
+    }
+
+    /// Returns path of the field which failed to deserialize (empty if not recorded)
+    #[inline]
+    pub fn path(&self) -> FieldPath<'_> {
+        #[cfg(feature = "alloc")]
+        {
+            match self.context {
+                None => FieldPath::default(),
+                Some(ref context) => FieldPath {
+                    root: context.root,
+                    segments: &context.path,
+                },
+            }
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            FieldPath::default()
+        }
+    }
+
+    /// Adds `offset` to byte offset of current error. Used when propagating an error of a value which starts at
+    /// `offset` in its container.
+    #[inline]
+    pub fn with_offset(self, offset: usize) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            self.map_context(|context| context.offset += offset)
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = offset;
+            self
+        }
+    }
+
+    /// Sets name of the type being decoded (if not already set by an inner value)
+    #[inline]
+    pub fn with_type_name(self, type_name: &'static str) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            self.map_context(|context| {
+                context.type_name.get_or_insert(type_name);
+            })
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = type_name;
+            self
+        }
+    }
+
+    /// Prepends field `field` of type `parent` to path of current error
+    #[inline]
+    pub fn with_field(self, parent: &'static str, field: &'static str) -> Self {
+        self.with_segment(Some(parent), PathSegment::Field(field))
+    }
+
+    /// Prepends variant `variant` of enum `parent` to path of current error
+    #[inline]
+    pub fn with_variant(self, parent: &'static str, variant: &'static str) -> Self {
+        self.with_segment(Some(parent), PathSegment::Variant(variant))
+    }
+
+    /// Prepends element `index` of a sequence to path of current error
+    #[inline]
+    pub fn with_index(self, index: usize) -> Self {
+        self.with_segment(None, PathSegment::Index(index))
+    }
+
+    #[inline]
+    fn with_segment(self, root: Option<&'static str>, segment: PathSegment) -> Self {
+        #[cfg(feature = "alloc")]
+        {
+            self.map_context(|context| {
+                context.root = root;
+                context.path.push(segment);
+            })
+        }
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            let _ = (root, segment);
+            self
+        }
+    }
+
+    /// Sets underlying cause of current error (returned by `std::error::Error::source()`)
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn with_source<E>(self, source: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        self.map_context(|context| context.source = Some(Box::new(source)))
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn map_context<F: FnOnce(&mut Context)>(mut self, f: F) -> Self {
+        f(self.context.get_or_insert_with(Default::default));
+        self
+    }
 }
 
 impl From<ErrorKind> for Error {
     #[inline]
     fn from(kind: ErrorKind) -> Self {
-        Self { kind }
+        Self {
+            kind,
+            #[cfg(feature = "alloc")]
+            context: None,
+        }
     }
 }
 
 impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        if let Some(offset) = self.offset() {
+            write!(f, " at offset {}", offset)?;
+        }
+
+        let path = self.path();
+
+        if !path.is_empty() {
+            write!(f, " in `{}`", path)?;
+        }
+
+        if let Some(type_name) = self.type_name() {
+            write!(f, " while decoding `{}`", type_name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Segment of a [`FieldPath`](self::FieldPath)
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PathSegment {
+    /// Named (or positional) field of a struct or enum variant
+    Field(&'static str),
+    /// Variant of an enum
+    Variant(&'static str),
+    /// Element of an array or a sequence
+    Index(usize),
+}
+
+/// Path of a field in a value (e.g., `Order.lines[3].sku`)
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct FieldPath<'a> {
+    root: Option<&'static str>,
+    /// Segments of path, from innermost to outermost
+    segments: &'a [PathSegment],
+}
+
+impl<'a> FieldPath<'a> {
+    /// Returns name of the type at the start of path (if any)
+    #[inline]
+    pub fn root(&self) -> Option<&'static str> {
+        self.root
+    }
+
+    /// Returns segments of path, from outermost to innermost
     #[inline]
+    pub fn segments(&self) -> Rev<Iter<'a, PathSegment>> {
+        self.segments.iter().rev()
+    }
+
+    /// Returns `true` if path has no segments
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+impl Display for FieldPath<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{}", self.kind)
+        if let Some(root) = self.root {
+            write!(f, "{}", root)?;
+        }
+
+        for segment in self.segments() {
+            match segment {
+                PathSegment::Field(name) => write!(f, ".{}", name)?,
+                PathSegment::Variant(name) => write!(f, "::{}", name)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+
+        Ok(())
     }
 }
 
 /// Different variants of possible errors
+///
+/// New variants may be added in future releases, so matching on this enum requires a wildcard arm.
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[non_exhaustive]
 pub enum ErrorKind {
     /// Returned when deserialization from bytes to char fails.
     InvalidChar,
@@ -43,11 +287,47 @@ pub enum ErrorKind {
     #[cfg(feature = "dynamic")]
     InvalidStr,
     /// Returned when input slice is of invalid length.
-    #[cfg(feature = "dynamic")]
     InvalidSliceLength,
     /// Returned when input slice cannot be de-serialized into given type.
     #[cfg(feature = "dynamic")]
     InvalidInput,
+    /// Returned when layout version of input is not known to given type.
+    UnknownVersion,
+    /// Returned when reserved bytes of input are not zero (only in strict mode).
+    NonZeroReserved,
+    /// Returned when byte of a `bool` is neither `0` nor `1` (only in strict mode).
+    InvalidBool,
+    /// Returned when padding bytes after a variant of an enum are not zero (only in strict mode).
+    NonZeroPadding,
+    /// Returned when bits of input not used by its encoding are not zero (only in strict mode).
+    NonZeroUnusedBits,
+    /// Returned when tag of input does not match any variant of given enum.
+    InvalidTag,
+    /// Returned when checksum stored in input does not match checksum of its bytes.
+    ChecksumMismatch,
+    /// Returned when length of a frame is greater than maximum frame size.
+    #[cfg(feature = "dynamic")]
+    FrameTooLarge,
+    /// Returned when index of a record is out of bounds.
+    IndexOutOfBounds,
+    /// Returned when input exceeds limits of deserialization.
+    #[cfg(feature = "dynamic")]
+    LimitExceeded,
+    /// Returned when bytes are left in input after deserializing a value which should span whole input.
+    #[cfg(feature = "dynamic")]
+    TrailingBytes {
+        /// Number of bytes left after the value
+        remaining: usize,
+    },
+    /// Returned when encoding of a [`Varint`](crate::Varint) is longer than necessary or its value overflows.
+    #[cfg(feature = "dynamic")]
+    InvalidVarint,
+    /// Returned when header of a record file does not match its record type.
+    #[cfg(feature = "std")]
+    HeaderMismatch,
+    /// Returned when an I/O operation on underlying reader or writer fails.
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
 }
 
 impl Display for ErrorKind {
@@ -56,12 +336,45 @@ impl Display for ErrorKind {
             ErrorKind::InvalidChar => write!(f, "Deserialization from bytes to char failed"),
             #[cfg(feature = "dynamic")]
             ErrorKind::InvalidStr => write!(f, "Deserialization from bytes to String failed"),
-            #[cfg(feature = "dynamic")]
             ErrorKind::InvalidSliceLength => write!(f, "Input slice is of invalid length"),
             #[cfg(feature = "dynamic")]
             ErrorKind::InvalidInput => {
                 write!(f, "input slice cannot be de-serialized into given type")
             }
+            ErrorKind::UnknownVersion => write!(f, "Layout version of input is unknown"),
+            ErrorKind::NonZeroReserved => write!(f, "Reserved bytes of input are not zero"),
+            ErrorKind::InvalidBool => write!(f, "Byte of bool is neither 0 nor 1"),
+            ErrorKind::NonZeroPadding => write!(f, "Padding bytes of enum variant are not zero"),
+            ErrorKind::NonZeroUnusedBits => write!(f, "Unused bits of input are not zero"),
+            ErrorKind::InvalidTag => write!(f, "Tag of input does not match any variant"),
+            ErrorKind::ChecksumMismatch => write!(f, "Checksum of input does not match"),
+            #[cfg(feature = "dynamic")]
+            ErrorKind::FrameTooLarge => write!(f, "Length of frame exceeds maximum frame size"),
+            ErrorKind::IndexOutOfBounds => write!(f, "Index of record is out of bounds"),
+            #[cfg(feature = "dynamic")]
+            ErrorKind::LimitExceeded => write!(f, "Input exceeds limits of deserialization"),
+            #[cfg(feature = "dynamic")]
+            ErrorKind::TrailingBytes { remaining } => {
+                write!(f, "Input has {} trailing bytes after value", remaining)
+            }
+            #[cfg(feature = "dynamic")]
+            ErrorKind::InvalidVarint => {
+                write!(f, "Varint encoding of input is overlong or overflows")
+            }
+            #[cfg(feature = "std")]
+            ErrorKind::HeaderMismatch => write!(f, "Header of record file does not match"),
+            #[cfg(feature = "std")]
+            ErrorKind::Io(kind) => write!(f, "I/O operation failed: {}", kind),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self.context.as_ref()?.source {
+            None => None,
+            Some(ref source) => Some(&**source),
         }
     }
 }
@@ -69,9 +382,69 @@ impl Display for ErrorKind {
 #[cfg(feature = "dynamic")]
 impl From<FromUtf8Error> for Error {
     #[inline]
-    fn from(_: FromUtf8Error) -> Error {
-        Error {
-            kind: ErrorKind::InvalidStr,
+    fn from(err: FromUtf8Error) -> Error {
+        let error = Error::from(ErrorKind::InvalidStr);
+
+        #[cfg(feature = "std")]
+        let error = error.with_source(err);
+        #[cfg(not(feature = "std"))]
+        let _ = err;
+
+        error
+    }
+}
+
+#[cfg(feature = "dynamic")]
+impl From<Utf8Error> for Error {
+    #[inline]
+    fn from(err: Utf8Error) -> Error {
+        let error = Error::from(ErrorKind::InvalidStr);
+
+        #[cfg(feature = "std")]
+        let error = error.with_source(err);
+        #[cfg(not(feature = "std"))]
+        let _ = err;
+
+        error
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn check_utf8_source() {
+        let err = Error::from(String::from_utf8(vec![0xFF]).unwrap_err());
+
+        assert_eq!(ErrorKind::InvalidStr, err.kind());
+
+        let source = err.source().unwrap();
+        assert!(source.is::<FromUtf8Error>());
+        assert_eq!(
+            String::from_utf8(vec![0xFF]).unwrap_err().to_string(),
+            source.to_string()
+        );
+    }
+
+    #[test]
+    fn check_no_source() {
+        assert!(Error::from(ErrorKind::InvalidTag).source().is_none());
+    }
+
+    #[test]
+    fn check_boxed_error() {
+        fn parse() -> core::result::Result<u16, std::boxed::Box<dyn StdError + Send + Sync>> {
+            Ok(<u16 as crate::DesseDynamic>::deserialize_from(&[1][..])?)
         }
+
+        assert_eq!(
+            "Input slice is of invalid length",
+            parse().unwrap_err().to_string()
+        );
     }
 }
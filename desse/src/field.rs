@@ -0,0 +1,179 @@
+use core::cmp::Ordering;
+use core::marker::PhantomData;
+
+use crate::{DesseSized, DesseStatic};
+
+/// Trait for types whose serialized bytes can be compared without deserializing them, giving the same ordering as
+/// the deserialized values
+///
+/// This trait is a bound of [`RecordSlice::binary_search_by_field()`](crate::RecordSlice::binary_search_by_field)
+/// and [`RecordSliceMut::sort_by_field()`](crate::RecordSliceMut::sort_by_field), so that records can only be ordered
+/// by fields whose encoding preserves order.
+pub trait OrderPreserving: DesseStatic + DesseSized {
+    /// Compares serialized bytes (of length `Self::SIZE`) of two values
+    fn cmp_serialized(a: &[u8], b: &[u8]) -> Ordering;
+}
+
+macro_rules! impl_order_preserving_unsigned {
+    ($type: ty) => {
+        impl OrderPreserving for $type {
+            /// Little endian bytes are compared starting from the most significant byte
+            #[inline]
+            fn cmp_serialized(a: &[u8], b: &[u8]) -> Ordering {
+                a.iter().rev().cmp(b.iter().rev())
+            }
+        }
+    };
+}
+
+macro_rules! impl_order_preserving_signed {
+    ($type: ty) => {
+        impl OrderPreserving for $type {
+            /// Little endian bytes are compared starting from the most significant byte, with its sign bit flipped
+            #[inline]
+            fn cmp_serialized(a: &[u8], b: &[u8]) -> Ordering {
+                let (a_msb, a_rest) = a.split_last().unwrap();
+                let (b_msb, b_rest) = b.split_last().unwrap();
+
+                (a_msb ^ 0x80)
+                    .cmp(&(b_msb ^ 0x80))
+                    .then_with(|| a_rest.iter().rev().cmp(b_rest.iter().rev()))
+            }
+        }
+    };
+}
+
+impl_order_preserving_unsigned!(u8);
+impl_order_preserving_unsigned!(u16);
+impl_order_preserving_unsigned!(u32);
+impl_order_preserving_unsigned!(u64);
+impl_order_preserving_unsigned!(u128);
+impl_order_preserving_unsigned!(char);
+
+impl_order_preserving_signed!(i8);
+impl_order_preserving_signed!(i16);
+impl_order_preserving_signed!(i32);
+impl_order_preserving_signed!(i64);
+impl_order_preserving_signed!(i128);
+
+impl OrderPreserving for bool {
+    #[inline]
+    fn cmp_serialized(a: &[u8], b: &[u8]) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<const N: usize> OrderPreserving for [u8; N]
+where
+    [u8; N]: DesseStatic + DesseSized,
+{
+    #[inline]
+    fn cmp_serialized(a: &[u8], b: &[u8]) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Location of a field of type `F` in serialized bytes of record type `R`
+///
+/// Field descriptors of a struct are generated by `DesseSized` derive macro, when opted in with
+/// `#[desse(field_consts)]`, as associated constants named `FIELD_<NAME>` (e.g., `FIELD_ID` for field `id` and
+/// `FIELD_0` for first field of a tuple struct). Each constant has same visibility as its field.
+///
+/// # Example
+///
+/// ```
+/// use desse::{DesseSized, DesseStatic};
+///
+/// #[derive(DesseStatic, DesseSized)]
+/// #[desse(field_consts)]
+/// struct Entry {
+///     flags: u8,
+///     id: u32,
+/// }
+///
+/// let entry = Entry { flags: 1, id: 42 };
+/// let bytes = entry.serialize();
+///
+/// assert_eq!(1, Entry::FIELD_ID.offset());
+/// assert_eq!(&42u32.to_le_bytes(), Entry::FIELD_ID.bytes(&bytes));
+/// ```
+#[derive(Debug)]
+pub struct Field<R, F> {
+    offset: usize,
+    _marker: PhantomData<fn() -> (R, F)>,
+}
+
+impl<R, F> Clone for Field<R, F> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R, F> Copy for Field<R, F> {}
+
+impl<R, F> Field<R, F>
+where
+    R: DesseSized,
+    F: DesseSized,
+{
+    /// Creates a new field descriptor for field starting at `offset` bytes in serialized record
+    ///
+    /// Prefer the constants generated by derive macro over calling this function directly. A wrong `offset` results
+    /// in comparing wrong bytes (or panic in case `offset + F::SIZE > R::SIZE`).
+    #[inline]
+    pub const fn new(offset: usize) -> Self {
+        Self {
+            offset,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns offset of field in serialized record
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns serialized bytes of field from serialized bytes of record
+    ///
+    /// # Panic
+    ///
+    /// Panics if `record` is shorter than `offset() + F::SIZE`.
+    #[inline]
+    pub fn bytes<'a>(&self, record: &'a [u8]) -> &'a [u8] {
+        &record[self.offset..(self.offset + F::SIZE)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_order<T: OrderPreserving + Ord>(values: &[T])
+    where
+        T::Output: AsRef<[u8]>,
+    {
+        for a in values {
+            for b in values {
+                assert_eq!(
+                    a.cmp(b),
+                    T::cmp_serialized(a.serialize().as_ref(), b.serialize().as_ref())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn check_order_preserving() {
+        check_order(&[0u8, 1, 127, 128, 255]);
+        check_order(&[0u16, 1, 255, 256, 0x1234, u16::MAX]);
+        check_order(&[0u64, 1, 256, 1 << 40, u64::MAX - 1, u64::MAX]);
+        check_order(&[i8::MIN, -1, 0, 1, i8::MAX]);
+        check_order(&[i32::MIN, -65536, -256, -1, 0, 1, 255, 256, i32::MAX]);
+        check_order(&[i128::MIN, -1, 0, 1, i128::MAX]);
+        check_order(&['\0', 'a', 'z', 'é', '😀']);
+        check_order(&[false, true]);
+        check_order(&[[0u8, 1], [0, 2], [1, 0], [255, 255]]);
+    }
+}
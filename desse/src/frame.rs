@@ -0,0 +1,350 @@
+#![cfg(feature = "dynamic")]
+use crate::checksum::ChecksumWriter;
+use crate::{Crc32c, DesseDynamic, DesseOwned, DesseSized, ErrorKind, Reader, Result, Writer};
+
+/// Default maximum size of payload of a frame (16 MiB)
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// Size of CRC32C trailer of a checksummed frame
+const CHECKSUM_SIZE: usize = <u32>::SIZE;
+
+/// Writes [`DesseDynamic`](crate::DesseDynamic) values as frames in any [`Writer`](crate::Writer)
+///
+/// Each value is written as a frame: length of serialized value (encoded as `u64`) followed by the serialized value.
+/// When checksum is enabled, CRC32C of length prefix and serialized value (encoded as `u32`) is appended to each frame.
+///
+/// # Example
+///
+/// ```
+/// use desse::{FrameReader, FrameWriter};
+///
+/// let mut writer = FrameWriter::new(Vec::new());
+/// writer.write(&"hello").unwrap();
+/// writer.write(&42u32).unwrap();
+///
+/// let bytes = writer.into_inner();
+/// let mut reader = FrameReader::new(&*bytes);
+///
+/// reader.skip_frame().unwrap();
+/// assert_eq!(42, reader.read::<u32>().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct FrameWriter<W: Writer> {
+    inner: W,
+    max_frame_size: usize,
+    checksum: bool,
+}
+
+impl<W: Writer> FrameWriter<W> {
+    /// Creates a new frame writer with maximum frame size of [`DEFAULT_MAX_FRAME_SIZE`](self::DEFAULT_MAX_FRAME_SIZE)
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self::with_max_frame_size(inner, DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Creates a new frame writer with given maximum frame size
+    #[inline]
+    pub fn with_max_frame_size(inner: W, max_frame_size: usize) -> Self {
+        Self {
+            inner,
+            max_frame_size,
+            checksum: false,
+        }
+    }
+
+    /// Returns maximum size of payload of a frame
+    #[inline]
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Returns `true` if frames have a CRC32C trailer
+    #[inline]
+    pub fn checksum(&self) -> bool {
+        self.checksum
+    }
+
+    /// Enables or disables CRC32C trailer of frames (disabled by default)
+    #[inline]
+    pub fn set_checksum(&mut self, checksum: bool) {
+        self.checksum = checksum;
+    }
+
+    /// Returns a reference to underlying writer
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns underlying writer
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes `value` as a frame
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::FrameTooLarge`) when serialized size of `value` is greater than
+    /// maximum frame size. Nothing is written in this case.
+    pub fn write<T: DesseDynamic>(&mut self, value: &T) -> Result<()> {
+        let len = value.serialized_size();
+
+        if len > self.max_frame_size {
+            return Err(ErrorKind::FrameTooLarge.into());
+        }
+
+        if !self.checksum {
+            DesseDynamic::serialize_into(&(len as u64), &mut self.inner)?;
+            return value.serialize_into(&mut self.inner);
+        }
+
+        let mut writer = ChecksumWriter::new(&mut self.inner);
+
+        DesseDynamic::serialize_into(&(len as u64), &mut writer)?;
+        value.serialize_into(&mut writer)?;
+
+        let checksum = writer.finish();
+        DesseDynamic::serialize_into(&checksum, &mut self.inner)
+    }
+}
+
+/// Reads [`DesseDynamic`](crate::DesseDynamic) values written by [`FrameWriter`](self::FrameWriter) from any
+/// [`Reader`](crate::Reader)
+///
+/// Payload of a frame is read completely (and its checksum is verified, when enabled) before deserializing its value.
+/// So, when deserialization of a value fails, reader is positioned at the start of next frame and can continue reading.
+#[derive(Debug)]
+pub struct FrameReader<R: Reader> {
+    inner: R,
+    max_frame_size: usize,
+    checksum: bool,
+}
+
+impl<R: Reader> FrameReader<R> {
+    /// Creates a new frame reader with maximum frame size of [`DEFAULT_MAX_FRAME_SIZE`](self::DEFAULT_MAX_FRAME_SIZE)
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        Self::with_max_frame_size(inner, DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    /// Creates a new frame reader with given maximum frame size
+    #[inline]
+    pub fn with_max_frame_size(inner: R, max_frame_size: usize) -> Self {
+        Self {
+            inner,
+            max_frame_size,
+            checksum: false,
+        }
+    }
+
+    /// Returns maximum size of payload of a frame
+    #[inline]
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Returns `true` if frames have a CRC32C trailer
+    #[inline]
+    pub fn checksum(&self) -> bool {
+        self.checksum
+    }
+
+    /// Enables or disables CRC32C trailer of frames (disabled by default)
+    #[inline]
+    pub fn set_checksum(&mut self, checksum: bool) {
+        self.checksum = checksum;
+    }
+
+    /// Returns a reference to underlying reader
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns underlying reader
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads next frame and deserializes its value
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::FrameTooLarge`) when length of frame is greater than maximum frame
+    /// size (payload of frame is not consumed in this case), error (`ErrorKind::ChecksumMismatch`) when checksum of
+    /// frame does not match and error (`ErrorKind::TrailingBytes`) when value does not consume complete payload of
+    /// frame.
+    pub fn read<T: DesseOwned>(&mut self) -> Result<T::Output> {
+        let payload = self.read_payload()?;
+        T::deserialize_exact(payload)
+    }
+
+    /// Skips next frame without deserializing its payload
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::FrameTooLarge`) when length of frame is greater than maximum frame
+    /// size (payload of frame is not consumed in this case) and error (`ErrorKind::ChecksumMismatch`) when checksum of
+    /// frame does not match.
+    #[inline]
+    pub fn skip_frame(&mut self) -> Result<()> {
+        self.read_payload().map(|_| ())
+    }
+
+    /// Reads length prefix and payload of next frame
+    fn read_payload(&mut self) -> Result<&[u8]> {
+        let len = <u64 as DesseOwned>::deserialize_owned(&mut self.inner)?;
+
+        if len > self.max_frame_size as u64 {
+            return Err(ErrorKind::FrameTooLarge.into());
+        }
+
+        let len = len as usize;
+
+        if !self.checksum {
+            return self.inner.read(len);
+        }
+
+        let frame_len = len
+            .checked_add(CHECKSUM_SIZE)
+            .ok_or(ErrorKind::FrameTooLarge)?;
+        let (payload, mut checksum) = self.inner.read(frame_len)?.split_at(len);
+
+        let mut hasher = Crc32c::new();
+        hasher.update(&(len as u64).to_le_bytes());
+        hasher.update(payload);
+
+        if hasher.finish() != <u32 as DesseOwned>::deserialize_owned(&mut checksum)? {
+            return Err(ErrorKind::ChecksumMismatch.into());
+        }
+
+        Ok(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::DesseSized;
+
+    const LEN_SIZE: usize = <u64>::SIZE;
+
+    #[test]
+    fn check_frame_roundtrip() {
+        let mut writer = FrameWriter::new(Vec::new());
+        writer.write(&"hello").unwrap();
+        writer.write(&vec![1u16, 2, 3]).unwrap();
+        writer.write(&7u8).unwrap();
+
+        let bytes = writer.into_inner();
+        assert_eq!(LEN_SIZE + 13 + LEN_SIZE + 14 + LEN_SIZE + 1, bytes.len());
+
+        let mut reader = FrameReader::new(&*bytes);
+        assert_eq!("hello", reader.read::<String>().unwrap());
+        assert_eq!(vec![1, 2, 3], reader.read::<Vec<u16>>().unwrap());
+        assert_eq!(7, reader.read::<u8>().unwrap());
+        assert!(reader.into_inner().is_empty());
+    }
+
+    #[test]
+    fn check_skip_frame() {
+        let mut writer = FrameWriter::new(Vec::new());
+        writer.write(&"skipped".to_string()).unwrap();
+        writer.write(&5u32).unwrap();
+
+        let bytes = writer.into_inner();
+        let mut reader = FrameReader::new(&*bytes);
+
+        reader.skip_frame().unwrap();
+        assert_eq!(5, reader.read::<u32>().unwrap());
+
+        let err = reader.skip_frame().unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+    }
+
+    #[test]
+    fn check_bad_frame() {
+        let mut writer = FrameWriter::new(Vec::new());
+        writer.write(&vec![0xFFu8]).unwrap();
+        writer.write(&5u64).unwrap();
+        writer.write(&"ok").unwrap();
+
+        let bytes = writer.into_inner();
+        let mut reader = FrameReader::new(&*bytes);
+
+        assert_eq!(
+            ErrorKind::InvalidStr,
+            reader.read::<String>().unwrap_err().kind()
+        );
+
+        let err = reader.read::<u32>().unwrap_err();
+        assert_eq!(ErrorKind::TrailingBytes { remaining: 4 }, err.kind());
+        assert_eq!(Some(4), err.offset());
+
+        assert_eq!("ok", reader.read::<String>().unwrap());
+    }
+
+    #[test]
+    fn check_max_frame_size() {
+        let mut writer = FrameWriter::with_max_frame_size(Vec::new(), 4);
+        assert_eq!(4, writer.max_frame_size());
+
+        writer.write(&1u32).unwrap();
+        assert_eq!(
+            ErrorKind::FrameTooLarge,
+            writer.write(&1u64).unwrap_err().kind()
+        );
+        assert_eq!(LEN_SIZE + 4, writer.get_ref().len());
+
+        let mut large = Vec::new();
+        FrameWriter::new(&mut large).write(&1u64).unwrap();
+
+        let mut reader = FrameReader::with_max_frame_size(&*large, 4);
+        assert_eq!(
+            ErrorKind::FrameTooLarge,
+            reader.skip_frame().unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn check_checksum_frame() {
+        let mut writer = FrameWriter::new(Vec::new());
+        writer.set_checksum(true);
+        assert!(writer.checksum());
+
+        writer.write(&"hello").unwrap();
+        writer.write(&5u32).unwrap();
+        writer.write(&"world").unwrap();
+
+        let mut bytes = writer.into_inner();
+        assert_eq!(3 * (LEN_SIZE + CHECKSUM_SIZE) + 13 + 4 + 13, bytes.len());
+
+        let mut reader = FrameReader::new(&*bytes);
+        reader.set_checksum(true);
+
+        assert_eq!("hello", reader.read::<String>().unwrap());
+        assert_eq!(5, reader.read::<u32>().unwrap());
+        assert_eq!("world", reader.read::<String>().unwrap());
+
+        // Corrupt payload of second frame
+        bytes[LEN_SIZE + 13 + CHECKSUM_SIZE + LEN_SIZE] ^= 1;
+
+        let mut reader = FrameReader::new(&*bytes);
+        reader.set_checksum(true);
+
+        reader.skip_frame().unwrap();
+        assert_eq!(
+            ErrorKind::ChecksumMismatch,
+            reader.read::<u32>().unwrap_err().kind()
+        );
+        assert_eq!("world", reader.read::<String>().unwrap());
+    }
+}
@@ -0,0 +1,317 @@
+#![cfg(feature = "dynamic")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use crate::private::Sealed;
+use crate::{DesseOwned, ErrorKind, Reader, Result};
+
+/// Result of feeding bytes to an [`IncrementalDecoder`](self::IncrementalDecoder)
+#[derive(Debug, PartialEq)]
+pub enum DecodeStatus<T> {
+    /// At least given number of additional bytes are needed before a value can be decoded. All the fed bytes are
+    /// buffered by decoder.
+    NeedMore(usize),
+    /// A value is decoded. Second field contains the number of bytes consumed from the slice passed to last `feed()`;
+    /// remaining bytes are not buffered and should be fed again for decoding next value.
+    Done(T, usize),
+}
+
+/// Resumable decoder for [`DesseDynamic`](crate::DesseDynamic) values arriving in chunks
+///
+/// Decoder remembers the number of bytes needed by the read that failed last time, and does not attempt to decode
+/// again until that many bytes are buffered. A `String` (or a `Vec` of statically sized elements) is decoded only once
+/// after all of its bytes have arrived. Sequences check the minimum serialized size of their remaining elements before
+/// every element, so, for a sequence of dynamically sized elements (e.g. `Vec<String>`), the number of bytes needed
+/// grows geometrically and the buffer is re-parsed a number of times logarithmic in the number of elements (scaled by
+/// the ratio of their actual and minimum serialized size), instead of once per element.
+///
+/// # Example
+///
+/// ```
+/// use desse::{DecodeStatus, DesseDynamic, IncrementalDecoder};
+///
+/// let bytes = DesseDynamic::serialize(&"hello").unwrap();
+/// let mut decoder = IncrementalDecoder::<String>::new();
+///
+/// assert_eq!(DecodeStatus::NeedMore(5), decoder.feed(&bytes[..3]).unwrap());
+/// assert_eq!(DecodeStatus::NeedMore(5), decoder.feed(&bytes[3..8]).unwrap());
+/// assert_eq!(
+///     DecodeStatus::Done("hello".to_string(), 5),
+///     decoder.feed(&bytes[8..]).unwrap()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct IncrementalDecoder<T: DesseOwned> {
+    buf: Vec<u8>,
+    needed: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: DesseOwned> Default for IncrementalDecoder<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DesseOwned> IncrementalDecoder<T> {
+    /// Creates a new decoder
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            needed: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns number of buffered bytes
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Discards all the buffered bytes
+    #[inline]
+    pub fn reset(&mut self) {
+        self.buf.clear();
+        self.needed = 0;
+    }
+
+    /// Feeds next chunk of input to decoder
+    ///
+    /// # Error
+    ///
+    /// When deserialization fails for any reason other than insufficient input, error is returned and all the
+    /// buffered bytes are discarded.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result<DecodeStatus<T::Output>> {
+        let previous = self.buf.len();
+        self.buf.extend_from_slice(bytes);
+
+        if self.buf.len() < self.needed {
+            return Ok(DecodeStatus::NeedMore(self.needed - self.buf.len()));
+        }
+
+        let mut reader = ProbeReader {
+            bytes: &self.buf,
+            position: 0,
+            required: 0,
+        };
+
+        match T::deserialize_from(&mut reader) {
+            Ok(value) => {
+                let consumed = reader.position - previous;
+                self.reset();
+
+                Ok(DecodeStatus::Done(value, consumed))
+            }
+            Err(err)
+                if err.kind() == ErrorKind::InvalidSliceLength
+                    && reader.required > self.buf.len() =>
+            {
+                self.needed = reader.required;
+                Ok(DecodeStatus::NeedMore(self.needed - self.buf.len()))
+            }
+            Err(err) => {
+                self.reset();
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Reader over buffered bytes which records the number of bytes required by a read that could not be served (or by
+/// any `ensure()` before it)
+struct ProbeReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    required: usize,
+}
+
+impl ProbeReader<'_> {
+    /// Returns end position of a read of `len` bytes if it can be served
+    #[inline]
+    fn check(&mut self, len: usize) -> Result<usize> {
+        let end = self
+            .position
+            .checked_add(len)
+            .ok_or(ErrorKind::InvalidSliceLength)?;
+
+        if end > self.bytes.len() {
+            self.required = self.required.max(end);
+            return Err(ErrorKind::InvalidSliceLength.into());
+        }
+
+        Ok(end)
+    }
+}
+
+impl Sealed for ProbeReader<'_> {}
+
+impl Reader for ProbeReader<'_> {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
+    #[inline]
+    fn read(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self.check(len)?;
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn read_unchecked(&mut self, len: usize) -> Result<&[u8]> {
+        self.read(len)
+    }
+
+    #[inline]
+    fn is_at_end(&mut self) -> Result<bool> {
+        Ok(self.position == self.bytes.len())
+    }
+
+    /// More bytes may arrive later, so, this only records the number of bytes required (used as a lower bound for
+    /// next attempt, when reading fails later) instead of failing
+    #[inline]
+    fn ensure(&mut self, len: usize) -> Result<()> {
+        let end = self
+            .position
+            .checked_add(len)
+            .ok_or(ErrorKind::InvalidSliceLength)?;
+
+        self.required = self.required.max(end);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    use crate::DesseDynamic;
+
+    #[test]
+    fn check_byte_by_byte() {
+        let v = vec!["hello".to_string(), "".to_string(), "world".to_string()];
+        let bytes = DesseDynamic::serialize(&v).unwrap();
+
+        let mut decoder = IncrementalDecoder::<Vec<String>>::new();
+
+        for byte in &bytes[..(bytes.len() - 1)] {
+            match decoder.feed(&[*byte]).unwrap() {
+                DecodeStatus::NeedMore(n) => assert!(n > 0),
+                DecodeStatus::Done(..) => panic!("Decoded before all bytes are fed"),
+            }
+        }
+
+        assert_eq!(
+            DecodeStatus::Done(v, 1),
+            decoder.feed(&bytes[(bytes.len() - 1)..]).unwrap()
+        );
+        assert_eq!(0, decoder.buffered());
+    }
+
+    #[test]
+    fn check_large_payload_is_not_reparsed() {
+        let v = vec![7u32; 1000];
+        let bytes = DesseDynamic::serialize(&v).unwrap();
+
+        let mut decoder = IncrementalDecoder::<Vec<u32>>::new();
+        assert_eq!(
+            DecodeStatus::NeedMore(4000),
+            decoder.feed(&bytes[..8]).unwrap()
+        );
+
+        for chunk in bytes[8..(bytes.len() - 100)].chunks(100) {
+            let needed = decoder.needed;
+            decoder.feed(chunk).unwrap();
+
+            // Required length is known upfront, so it never changes while bytes are arriving
+            assert_eq!(needed, decoder.needed);
+        }
+
+        assert_eq!(
+            DecodeStatus::Done(v, 100),
+            decoder.feed(&bytes[(bytes.len() - 100)..]).unwrap()
+        );
+    }
+
+    /// Returns total number of bytes parsed by decoder when `bytes` are fed in chunks of 64 bytes
+    fn parsed_bytes<T: DesseOwned>(bytes: &[u8]) -> usize {
+        let mut decoder = IncrementalDecoder::<T>::new();
+        let mut parsed = 0;
+
+        for chunk in bytes.chunks(64) {
+            let buffered = decoder.buffered() + chunk.len();
+
+            if buffered >= decoder.needed {
+                parsed += buffered;
+            }
+
+            if let DecodeStatus::Done(..) = decoder.feed(chunk).unwrap() {
+                return parsed;
+            }
+        }
+
+        panic!("Value is not decoded after all bytes are fed");
+    }
+
+    #[test]
+    fn check_nested_payload_scaling() {
+        let v = (0..4000).map(|i| i.to_string()).collect::<Vec<_>>();
+
+        let small = DesseDynamic::serialize(&&v[..1000]).unwrap();
+        let large = DesseDynamic::serialize(&v).unwrap();
+
+        let small_parsed = parsed_bytes::<Vec<String>>(&small);
+        let large_parsed = parsed_bytes::<Vec<String>>(&large);
+
+        // Each element is re-parsed a few times, not once per element that arrives after it
+        assert!(small_parsed < 8 * small.len(), "{}", small_parsed);
+        assert!(large_parsed < 8 * large.len(), "{}", large_parsed);
+    }
+
+    #[test]
+    fn check_consumed() {
+        let mut bytes = DesseDynamic::serialize(&"abc").unwrap();
+        bytes.extend(DesseDynamic::serialize(&5u16).unwrap());
+
+        let mut decoder = IncrementalDecoder::<String>::new();
+        assert_eq!(
+            DecodeStatus::NeedMore(1),
+            decoder.feed(&bytes[..10]).unwrap()
+        );
+        assert_eq!(
+            DecodeStatus::Done("abc".to_string(), 1),
+            decoder.feed(&bytes[10..]).unwrap()
+        );
+
+        let mut decoder = IncrementalDecoder::<u16>::new();
+        assert_eq!(
+            DecodeStatus::Done(5, 2),
+            decoder.feed(&bytes[11..]).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_invalid_input() {
+        let mut decoder = IncrementalDecoder::<Option<u8>>::new();
+
+        assert_eq!(
+            ErrorKind::InvalidInput,
+            decoder.feed(&[2]).unwrap_err().kind()
+        );
+        assert_eq!(0, decoder.buffered());
+        assert_eq!(
+            DecodeStatus::Done(Some(3), 2),
+            decoder.feed(&[1, 3]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,256 @@
+#![cfg(feature = "std")]
+use alloc::vec::Vec;
+use core::mem::ManuallyDrop;
+use core::ptr;
+use std::io::{self, BufRead, Read, Write};
+
+use crate::private::Sealed;
+use crate::{Error, ErrorKind, Reader, Result, Writer};
+
+/// Adapter implementing [`Reader`](crate::Reader) for any [`BufRead`](std::io::BufRead)
+///
+/// When requested bytes are already present in the buffer of underlying reader, they are returned without copying.
+/// Otherwise, they are copied in an internal buffer.
+///
+/// # Example
+///
+/// ```
+/// use std::io::BufReader;
+///
+/// use desse::{DesseDynamic, IoReader};
+///
+/// let bytes = DesseDynamic::serialize(&vec![1u32, 2, 3]).unwrap();
+/// let mut reader = IoReader::new(BufReader::new(&*bytes));
+///
+/// assert_eq!(vec![1, 2, 3], Vec::<u32>::deserialize_from(&mut reader).unwrap());
+/// ```
+#[derive(Debug)]
+pub struct IoReader<R: BufRead> {
+    inner: R,
+    pending: usize,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> IoReader<R> {
+    /// Creates a new reader
+    #[inline]
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            pending: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to underlying reader
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns underlying reader (all the bytes returned by this reader are consumed from underlying reader)
+    #[inline]
+    pub fn into_inner(self) -> R {
+        let mut this = ManuallyDrop::new(self);
+        this.consume_pending();
+
+        unsafe {
+            ptr::drop_in_place(&mut this.buf);
+            ptr::read(&this.inner)
+        }
+    }
+
+    #[inline]
+    fn consume_pending(&mut self) {
+        if self.pending != 0 {
+            self.inner.consume(self.pending);
+            self.pending = 0;
+        }
+    }
+}
+
+impl<R: BufRead> Drop for IoReader<R> {
+    #[inline]
+    fn drop(&mut self) {
+        self.consume_pending();
+    }
+}
+
+impl<R: BufRead> Sealed for IoReader<R> {}
+
+impl<R: BufRead> Reader for IoReader<R> {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
+    #[inline]
+    fn read(&mut self, len: usize) -> Result<&[u8]> {
+        self.consume_pending();
+
+        if self.inner.fill_buf()?.len() >= len {
+            self.pending = len;
+            return Ok(&self.inner.fill_buf()?[..len]);
+        }
+
+        // Buffer grows as bytes arrive, so that a bogus length does not allocate upfront
+        self.buf.clear();
+        (&mut self.inner)
+            .take(len as u64)
+            .read_to_end(&mut self.buf)?;
+
+        if self.buf.len() < len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+
+        Ok(&self.buf)
+    }
+
+    #[inline]
+    fn read_unchecked(&mut self, len: usize) -> Result<&[u8]> {
+        self.read(len)
+    }
+
+    #[inline]
+    fn is_at_end(&mut self) -> Result<bool> {
+        self.consume_pending();
+        Ok(self.inner.fill_buf()?.is_empty())
+    }
+}
+
+/// Adapter implementing [`Writer`](crate::Writer) for any [`Write`](std::io::Write)
+///
+/// # Example
+///
+/// ```
+/// use desse::{DesseDynamic, IoWriter};
+///
+/// let mut writer = IoWriter::new(Vec::new());
+/// DesseDynamic::serialize_into(&"hello", &mut writer).unwrap();
+///
+/// assert_eq!(DesseDynamic::serialize(&"hello").unwrap(), writer.into_inner());
+/// ```
+#[derive(Debug)]
+pub struct IoWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> IoWriter<W> {
+    /// Creates a new writer
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to underlying writer
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns underlying writer
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Sealed for IoWriter<W> {}
+
+impl<W: Write> Writer for IoWriter<W> {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<()> {
+        Ok(self.inner.write_all(buf)?)
+    }
+
+    #[inline]
+    fn write_unchecked(&mut self, buf: &[u8]) -> Result<()> {
+        self.write(buf)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    #[inline]
+    fn from(err: std::io::Error) -> Error {
+        Error::from(ErrorKind::Io(err.kind())).with_source(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use std::io::{self, BufReader};
+
+    use crate::DesseDynamic;
+
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn check_io_reader() {
+        let v = vec!["hello".to_string(), "world".to_string()];
+        let mut bytes = DesseDynamic::serialize(&v).unwrap();
+        bytes.push(7);
+
+        // Small capacity so that some reads do not fit in buffer of `BufReader`
+        let mut reader = IoReader::new(BufReader::with_capacity(4, &*bytes));
+
+        assert_eq!(v, Vec::<String>::deserialize_from(&mut reader).unwrap());
+        assert_eq!(7, u8::deserialize_from(&mut reader).unwrap());
+
+        let err = u8::deserialize_from(&mut reader).unwrap_err();
+        assert_eq!(ErrorKind::Io(io::ErrorKind::UnexpectedEof), err.kind());
+    }
+
+    #[test]
+    fn check_io_reader_bogus_length() {
+        let mut bytes = (u64::MAX / 2).to_le_bytes().to_vec();
+        bytes.extend_from_slice(b"abc");
+
+        let err = String::deserialize_from(IoReader::new(&*bytes)).unwrap_err();
+        assert_eq!(ErrorKind::Io(io::ErrorKind::UnexpectedEof), err.kind());
+    }
+
+    #[test]
+    fn check_io_reader_into_inner() {
+        let bytes = [1u8, 2, 3];
+        let mut reader = IoReader::new(&bytes[..]);
+
+        assert_eq!(1, u8::deserialize_from(&mut reader).unwrap());
+        assert_eq!(&[2, 3], reader.into_inner());
+    }
+
+    #[test]
+    fn check_io_writer() {
+        let mut writer = IoWriter::new(Vec::new());
+        DesseDynamic::serialize_into(&Some(5u16), &mut writer).unwrap();
+        assert_eq!(vec![1, 5, 0], writer.into_inner());
+
+        let err = DesseDynamic::serialize_into(&5u16, IoWriter::new(FailingWriter)).unwrap_err();
+        assert_eq!(ErrorKind::Io(io::ErrorKind::BrokenPipe), err.kind());
+
+        let source = std::error::Error::source(&err).unwrap();
+        assert!(source.is::<io::Error>());
+    }
+}
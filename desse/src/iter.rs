@@ -0,0 +1,298 @@
+use core::convert::TryFrom;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::slice::ChunksExact;
+
+use crate::record_slice::deserialize_record;
+#[cfg(feature = "dynamic")]
+use crate::{DesseOwned, Reader};
+use crate::{DesseSized, DesseStatic, ErrorKind, Result};
+
+/// Returns an iterator over back-to-back [`DesseDynamic`](crate::DesseDynamic) values in `reader`
+///
+/// Iteration stops cleanly when `reader` reaches end of input. A trailing partial value results in an error, after
+/// which iterator returns `None`.
+///
+/// # Example
+///
+/// ```
+/// use desse::DesseDynamic;
+///
+/// let mut bytes = Vec::new();
+/// DesseDynamic::serialize_into(&"hello", &mut bytes).unwrap();
+/// DesseDynamic::serialize_into(&"world", &mut bytes).unwrap();
+///
+/// let values = desse::iter::<String, _>(&*bytes).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(vec!["hello", "world"], values);
+/// ```
+#[cfg(feature = "dynamic")]
+#[inline]
+pub fn iter<T, R>(reader: R) -> Iter<T, R>
+where
+    T: DesseOwned,
+    R: Reader,
+{
+    Iter {
+        reader,
+        done: false,
+        _marker: PhantomData,
+    }
+}
+
+/// Iterator over back-to-back [`DesseDynamic`](crate::DesseDynamic) values (created by [`iter()`](self::iter))
+#[cfg(feature = "dynamic")]
+#[derive(Debug)]
+pub struct Iter<T, R> {
+    reader: R,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "dynamic")]
+impl<T, R> Iter<T, R> {
+    /// Returns underlying reader
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "dynamic")]
+impl<T, R> Iterator for Iter<T, R>
+where
+    T: DesseOwned,
+    R: Reader,
+{
+    type Item = Result<T::Output>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.reader.is_at_end() {
+            Ok(true) => {
+                self.done = true;
+                None
+            }
+            Ok(false) => {
+                let value = T::deserialize_from(&mut self.reader);
+                self.done = value.is_err();
+                Some(value)
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "dynamic")]
+impl<T, R> FusedIterator for Iter<T, R>
+where
+    T: DesseOwned,
+    R: Reader,
+{
+}
+
+/// Returns an iterator over back-to-back [`DesseStatic`](crate::DesseStatic) values in `bytes`
+///
+/// If length of `bytes` is not a multiple of `T::SIZE`, last item is an error (`ErrorKind::InvalidSliceLength`).
+///
+/// # Panic
+///
+/// Panics if `T::SIZE` is zero.
+///
+/// # Example
+///
+/// ```
+/// use desse::DesseStatic;
+///
+/// let mut bytes = Vec::new();
+/// bytes.extend(&1u16.serialize());
+/// bytes.extend(&2u16.serialize());
+///
+/// let iter = desse::iter_static::<u16>(&bytes);
+/// assert_eq!(2, iter.len());
+/// assert_eq!(vec![1, 2], iter.collect::<Result<Vec<_>, _>>().unwrap());
+/// ```
+#[inline]
+pub fn iter_static<T>(bytes: &[u8]) -> StaticIter<'_, T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: for<'a> TryFrom<&'a [u8]>,
+{
+    assert_ne!(0, T::SIZE, "Records of zero size cannot be iterated");
+
+    StaticIter {
+        chunks: bytes.chunks_exact(T::SIZE),
+        done: false,
+        _marker: PhantomData,
+    }
+}
+
+/// Iterator over back-to-back [`DesseStatic`](crate::DesseStatic) values (created by
+/// [`iter_static()`](self::iter_static))
+#[derive(Debug)]
+pub struct StaticIter<'a, T> {
+    chunks: ChunksExact<'a, u8>,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Iterator for StaticIter<'_, T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: for<'a> TryFrom<&'a [u8]>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.chunks.next() {
+            Some(chunk) => Some(deserialize_record(chunk)),
+            None => {
+                self.done = true;
+
+                if self.chunks.remainder().is_empty() {
+                    None
+                } else {
+                    Some(Err(ErrorKind::InvalidSliceLength.into()))
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for StaticIter<'_, T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: for<'a> TryFrom<&'a [u8]>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        if self.done {
+            0
+        } else {
+            self.chunks.len() + usize::from(!self.chunks.remainder().is_empty())
+        }
+    }
+}
+
+impl<T> FusedIterator for StaticIter<'_, T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: for<'a> TryFrom<&'a [u8]>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "dynamic")]
+    use crate::DesseDynamic;
+    #[cfg(feature = "dynamic")]
+    use alloc::string::String;
+    #[cfg(feature = "dynamic")]
+    use alloc::vec;
+    #[cfg(feature = "dynamic")]
+    use alloc::vec::Vec;
+
+    #[cfg(feature = "dynamic")]
+    #[test]
+    fn check_iter() {
+        let mut bytes = Vec::new();
+        DesseDynamic::serialize_into(&"hello", &mut bytes).unwrap();
+        DesseDynamic::serialize_into(&"", &mut bytes).unwrap();
+
+        let mut iter = iter::<String, _>(&*bytes);
+        assert_eq!("hello", iter.next().unwrap().unwrap());
+        assert_eq!("", iter.next().unwrap().unwrap());
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+
+        assert_eq!(0, super::iter::<String, _>(&[][..]).count());
+    }
+
+    #[cfg(feature = "dynamic")]
+    #[test]
+    fn check_iter_partial_record() {
+        let mut bytes = DesseDynamic::serialize(&vec![1u32, 2]).unwrap();
+        let len = bytes.len();
+        bytes.extend_from_within(..(len - 1));
+
+        let values = iter::<Vec<u32>, _>(&*bytes).collect::<Vec<_>>();
+
+        assert_eq!(2, values.len());
+        assert_eq!(vec![1, 2], *values[0].as_ref().unwrap());
+        assert_eq!(
+            ErrorKind::InvalidSliceLength,
+            values[1].as_ref().unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn check_iter_static() {
+        let mut bytes = [0u8; 3 * 4 + 1];
+        bytes[..4].copy_from_slice(&1u32.to_le_bytes());
+        bytes[4..8].copy_from_slice(&2u32.to_le_bytes());
+        bytes[8..12].copy_from_slice(&3u32.to_le_bytes());
+
+        let mut iter = iter_static::<u32>(&bytes[..12]);
+        assert_eq!(3, iter.len());
+        assert_eq!(1, iter.next().unwrap().unwrap());
+        assert_eq!(2, iter.len());
+        assert_eq!(2, iter.next().unwrap().unwrap());
+        assert_eq!(3, iter.next().unwrap().unwrap());
+        assert_eq!(0, iter.len());
+        assert!(iter.next().is_none());
+
+        let mut iter = iter_static::<u32>(&bytes);
+        assert_eq!(4, iter.len());
+        assert_eq!(3, iter.by_ref().take(3).filter(Result::is_ok).count());
+        assert_eq!(1, iter.len());
+        assert_eq!(
+            ErrorKind::InvalidSliceLength,
+            iter.next().unwrap().unwrap_err().kind()
+        );
+        assert_eq!(0, iter.len());
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Records of zero size cannot be iterated")]
+    fn check_iter_static_zero_size() {
+        struct Empty;
+
+        impl DesseSized for Empty {
+            const SIZE: usize = 0;
+        }
+
+        impl DesseStatic for Empty {
+            type Output = [u8; 0];
+
+            fn serialize(&self) -> Self::Output {
+                []
+            }
+
+            fn serialize_into(&self, _: &mut Self::Output) {}
+
+            fn deserialize_from(_: &Self::Output) -> Result<Self> {
+                Ok(Empty)
+            }
+        }
+
+        let _ = iter_static::<Empty>(&[]);
+    }
+}
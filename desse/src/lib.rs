@@ -58,32 +58,99 @@
 
 #![no_std]
 
-#[cfg(feature = "dynamic")]
+#[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+#[cfg(feature = "async")]
+pub mod async_codec;
+#[cfg(feature = "dynamic")]
+mod borrowed;
+#[cfg(feature = "c-header")]
+mod c_header;
+mod checksum;
+#[cfg(feature = "dynamic")]
+mod collections;
 #[cfg(feature = "dynamic")]
 mod desse_dynamic;
 mod desse_static;
 mod error;
+mod field;
+#[cfg(feature = "dynamic")]
+mod frame;
+#[cfg(feature = "dynamic")]
+mod incremental;
+#[cfg(feature = "std")]
+mod io;
+mod iter;
+#[cfg(feature = "dynamic")]
+mod limits;
+#[cfg(feature = "dynamic")]
+mod pointers;
 #[cfg(feature = "dynamic")]
 mod private;
 #[cfg(feature = "dynamic")]
 mod reader;
+#[cfg(feature = "std")]
+mod record_file;
+mod record_slice;
+#[cfg(feature = "dynamic")]
+mod sequence;
+#[cfg(feature = "dynamic")]
+mod varint;
 #[cfg(feature = "dynamic")]
 mod writer;
 
 #[cfg(feature = "dynamic")]
-pub use crate::desse_dynamic::DesseDynamic;
-pub use crate::desse_static::{DesseSized, DesseStatic};
-pub use crate::error::{Error, ErrorKind, Result};
+pub use crate::borrowed::DesseBorrowed;
+#[cfg(feature = "c-header")]
+pub use crate::c_header::{CField, CHeader, CVariant, DesseCType};
+#[cfg(feature = "dynamic")]
+pub use crate::checksum::Checksummed;
+pub use crate::checksum::{crc32c, Crc32c};
+#[cfg(feature = "std")]
+pub use crate::collections::Sorted;
+#[cfg(feature = "dynamic")]
+pub use crate::desse_dynamic::{DesseDynamic, DesseOwned};
+pub use crate::desse_static::{DesseSized, DesseStatic, DesseVersioned};
+pub use crate::error::{Error, ErrorKind, FieldPath, PathSegment, Result};
+pub use crate::field::{Field, OrderPreserving};
+#[cfg(feature = "dynamic")]
+pub use crate::frame::{FrameReader, FrameWriter, DEFAULT_MAX_FRAME_SIZE};
+#[cfg(feature = "dynamic")]
+pub use crate::incremental::{DecodeStatus, IncrementalDecoder};
+#[cfg(feature = "std")]
+pub use crate::io::{IoReader, IoWriter};
+#[cfg(feature = "dynamic")]
+pub use crate::iter::{iter, Iter};
+pub use crate::iter::{iter_static, StaticIter};
+#[cfg(feature = "dynamic")]
+pub use crate::limits::{LimitedReader, Limits};
 #[cfg(feature = "dynamic")]
 pub use crate::reader::Reader;
+#[cfg(feature = "std")]
+pub use crate::record_file::{RecordFile, RecordFileIter};
+pub use crate::record_slice::{RecordChunks, RecordSlice, RecordSliceMut};
+#[cfg(feature = "dynamic")]
+pub use crate::sequence::{
+    deserialize_chunked, serialize_iter, serialize_iter_chunked, SeekableWriter,
+};
+#[cfg(feature = "dynamic")]
+pub use crate::varint::Varint;
 #[cfg(feature = "dynamic")]
 pub use crate::writer::Writer;
 
 #[cfg(feature = "derive")]
 pub use desse_derive::*;
 
+/// Items used by code generated by derive macros (not part of public API)
+#[cfg(feature = "dynamic")]
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec::Vec;
+}
+
 /// Compares and returns maximum of two values.
 ///
 /// # Warning
@@ -0,0 +1,376 @@
+#![cfg(feature = "dynamic")]
+use crate::private::Sealed;
+use crate::{ErrorKind, Reader, Result, DEFAULT_MAX_FRAME_SIZE};
+
+/// Limits enforced while deserializing untrusted input with
+/// [`DesseDynamic::deserialize_from_with_limits()`](crate::DesseDynamic::deserialize_from_with_limits)
+///
+/// Exceeding any of the limits results in an error (`ErrorKind::LimitExceeded`) before any memory is allocated for
+/// offending value.
+///
+/// # Example
+///
+/// ```
+/// use desse::{DesseDynamic, ErrorKind, Limits};
+///
+/// // Length prefix of a `Vec` claiming `u64::MAX` elements
+/// let bytes = u64::MAX.to_le_bytes();
+///
+/// let limits = Limits::new().with_max_sequence_len(1024);
+/// let err = Vec::<u8>::deserialize_from_with_limits(&bytes[..], &limits).unwrap_err();
+///
+/// assert_eq!(ErrorKind::LimitExceeded, err.kind());
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Limits {
+    max_bytes: usize,
+    max_sequence_len: usize,
+    max_string_len: usize,
+    max_depth: usize,
+}
+
+impl Default for Limits {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Limits {
+    /// Default maximum number of bytes read while deserializing a value (16 MiB)
+    pub const DEFAULT_MAX_BYTES: usize = DEFAULT_MAX_FRAME_SIZE;
+
+    /// Default maximum number of elements in a sequence
+    pub const DEFAULT_MAX_SEQUENCE_LEN: usize = 1024 * 1024;
+
+    /// Default maximum length (in bytes) of a string
+    pub const DEFAULT_MAX_STRING_LEN: usize = 1024 * 1024;
+
+    /// Default maximum nesting depth of containers
+    pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+    /// Limits enforced by deserialization functions which do not take any limits (e.g.,
+    /// [`DesseDynamic::deserialize_from()`](crate::DesseDynamic::deserialize_from)). Only nesting depth is limited, so
+    /// that deeply nested input cannot overflow the stack.
+    pub(crate) const DEPTH_ONLY: Self = Self::unlimited().with_max_depth(Self::DEFAULT_MAX_DEPTH);
+
+    /// Creates new limits with default values
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+            max_sequence_len: Self::DEFAULT_MAX_SEQUENCE_LEN,
+            max_string_len: Self::DEFAULT_MAX_STRING_LEN,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Creates new limits which never fail
+    #[inline]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_bytes: usize::MAX,
+            max_sequence_len: usize::MAX,
+            max_string_len: usize::MAX,
+            max_depth: usize::MAX,
+        }
+    }
+
+    /// Sets maximum number of bytes read while deserializing a value
+    #[inline]
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Sets maximum number of elements in a sequence
+    #[inline]
+    pub const fn with_max_sequence_len(mut self, max_sequence_len: usize) -> Self {
+        self.max_sequence_len = max_sequence_len;
+        self
+    }
+
+    /// Sets maximum length (in bytes) of a string
+    #[inline]
+    pub const fn with_max_string_len(mut self, max_string_len: usize) -> Self {
+        self.max_string_len = max_string_len;
+        self
+    }
+
+    /// Sets maximum nesting depth of containers (e.g., `Vec<Vec<u8>>` has depth `2`)
+    #[inline]
+    pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns maximum number of bytes read while deserializing a value
+    #[inline]
+    pub const fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Returns maximum number of elements in a sequence
+    #[inline]
+    pub const fn max_sequence_len(&self) -> usize {
+        self.max_sequence_len
+    }
+
+    /// Returns maximum length (in bytes) of a string
+    #[inline]
+    pub const fn max_string_len(&self) -> usize {
+        self.max_string_len
+    }
+
+    /// Returns maximum nesting depth of containers
+    #[inline]
+    pub const fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+/// Reader which enforces [`Limits`](self::Limits) on values read from an inner [`Reader`](crate::Reader)
+///
+/// Number of bytes read is counted across all the values read from this reader.
+#[derive(Debug)]
+pub struct LimitedReader<R: Reader> {
+    inner: R,
+    limits: Limits,
+    bytes_read: usize,
+    depth: usize,
+}
+
+impl<R: Reader> LimitedReader<R> {
+    /// Creates a new limited reader
+    #[inline]
+    pub fn new(inner: R, limits: Limits) -> Self {
+        Self {
+            inner,
+            limits,
+            bytes_read: 0,
+            depth: 0,
+        }
+    }
+
+    /// Returns limits of current reader
+    #[inline]
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Returns number of bytes read so far
+    #[inline]
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read
+    }
+
+    /// Returns underlying reader
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Checks that `len` more bytes can be read without exceeding `max_bytes` (never fails when number of bytes is
+    /// unlimited, even for lengths which cannot be read at all, so that reading them fails with error of inner reader)
+    #[inline]
+    fn check_bytes(&self, len: usize) -> Result<()> {
+        if self.limits.max_bytes != usize::MAX && len > self.limits.max_bytes - self.bytes_read {
+            return Err(ErrorKind::LimitExceeded.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de> LimitedReader<&'de [u8]> {
+    /// Returns bytes of underlying slice which are not read yet
+    #[inline]
+    pub(crate) fn remaining(&self) -> &'de [u8] {
+        self.inner
+    }
+
+    /// Reads `len` bytes borrowed from underlying slice (used by [`DesseBorrowed`](crate::DesseBorrowed))
+    #[inline]
+    pub(crate) fn read_borrowed(&mut self, len: usize) -> Result<&'de [u8]> {
+        self.check_bytes(len)?;
+
+        if len > self.inner.len() {
+            return Err(ErrorKind::InvalidSliceLength.into());
+        }
+
+        let (bytes, rest) = self.inner.split_at(len);
+        self.inner = rest;
+        self.bytes_read += len;
+
+        Ok(bytes)
+    }
+}
+
+impl<R: Reader> Sealed for LimitedReader<R> {}
+
+impl<R: Reader> Reader for LimitedReader<R> {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
+    #[inline]
+    fn read(&mut self, len: usize) -> Result<&[u8]> {
+        self.check_bytes(len)?;
+        self.bytes_read += len;
+        self.inner.read(len)
+    }
+
+    #[inline]
+    fn read_unchecked(&mut self, len: usize) -> Result<&[u8]> {
+        self.check_bytes(len)?;
+        self.bytes_read += len;
+        self.inner.read_unchecked(len)
+    }
+
+    #[inline]
+    fn is_at_end(&mut self) -> Result<bool> {
+        self.inner.is_at_end()
+    }
+
+    #[inline]
+    fn ensure(&mut self, len: usize) -> Result<()> {
+        self.check_bytes(len)?;
+        self.inner.ensure(len)
+    }
+
+    #[inline]
+    fn check_sequence_len(&mut self, len: u64) -> Result<()> {
+        if len > self.limits.max_sequence_len as u64 {
+            return Err(ErrorKind::LimitExceeded.into());
+        }
+
+        self.inner.check_sequence_len(len)
+    }
+
+    #[inline]
+    fn check_string_len(&mut self, len: u64) -> Result<()> {
+        if len > self.limits.max_string_len as u64 {
+            return Err(ErrorKind::LimitExceeded.into());
+        }
+
+        self.inner.check_string_len(len)
+    }
+
+    #[inline]
+    fn enter_nested(&mut self) -> Result<()> {
+        if self.depth >= self.limits.max_depth {
+            return Err(ErrorKind::LimitExceeded.into());
+        }
+
+        self.inner.enter_nested()?;
+        self.depth += 1;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn exit_nested(&mut self) {
+        self.inner.exit_nested();
+        self.depth -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::DesseDynamic;
+
+    #[test]
+    fn check_max_bytes() {
+        let bytes = DesseDynamic::serialize(&vec![1u32, 2, 3]).unwrap();
+        let limits = Limits::new().with_max_bytes(bytes.len());
+
+        assert_eq!(
+            vec![1, 2, 3],
+            Vec::<u32>::deserialize_from_with_limits(&*bytes, &limits).unwrap()
+        );
+
+        let limits = limits.with_max_bytes(bytes.len() - 1);
+        let err = Vec::<u32>::deserialize_from_with_limits(&*bytes, &limits).unwrap_err();
+        assert_eq!(ErrorKind::LimitExceeded, err.kind());
+    }
+
+    #[test]
+    fn check_max_sequence_len() {
+        let bytes = DesseDynamic::serialize(&vec![1u8, 2, 3]).unwrap();
+        let limits = Limits::new().with_max_sequence_len(2);
+
+        let err = Vec::<u8>::deserialize_from_with_limits(&*bytes, &limits).unwrap_err();
+        assert_eq!(ErrorKind::LimitExceeded, err.kind());
+    }
+
+    #[test]
+    fn check_max_string_len() {
+        let bytes = DesseDynamic::serialize(&"hello").unwrap();
+
+        let limits = Limits::new().with_max_string_len(5);
+        assert_eq!(
+            "hello",
+            String::deserialize_from_with_limits(&*bytes, &limits).unwrap()
+        );
+
+        let limits = Limits::new().with_max_string_len(4);
+        let err = String::deserialize_from_with_limits(&*bytes, &limits).unwrap_err();
+        assert_eq!(ErrorKind::LimitExceeded, err.kind());
+    }
+
+    #[test]
+    fn check_max_depth() {
+        let value = vec![vec![Some(1u8)], vec![None]];
+        let bytes = DesseDynamic::serialize(&value).unwrap();
+
+        let limits = Limits::new().with_max_depth(3);
+        assert_eq!(
+            value,
+            Vec::<Vec<Option<u8>>>::deserialize_from_with_limits(&*bytes, &limits).unwrap()
+        );
+
+        let limits = Limits::new().with_max_depth(2);
+        let err =
+            Vec::<Vec<Option<u8>>>::deserialize_from_with_limits(&*bytes, &limits).unwrap_err();
+        assert_eq!(ErrorKind::LimitExceeded, err.kind());
+    }
+
+    #[test]
+    fn check_depth_is_restored() {
+        let mut bytes = DesseDynamic::serialize(&vec![vec![1u8]]).unwrap();
+        bytes.extend(DesseDynamic::serialize(&vec![vec![2u8]]).unwrap());
+
+        let mut reader = LimitedReader::new(&*bytes, Limits::new().with_max_depth(2));
+
+        for expected in 1..=2u8 {
+            assert_eq!(
+                vec![vec![expected]],
+                Vec::<Vec<u8>>::deserialize_from(&mut reader).unwrap()
+            );
+        }
+
+        assert_eq!(bytes.len(), reader.bytes_read());
+    }
+
+    #[test]
+    fn check_allocation_bomb() {
+        // A `Vec<u64>` claiming 2^60 elements followed by no elements
+        let bytes = (1u64 << 60).to_le_bytes();
+
+        let err =
+            Vec::<u64>::deserialize_from_with_limits(&bytes[..], &Limits::unlimited()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+
+        let err = Vec::<u64>::deserialize_from_with_limits(&bytes[..], &Limits::new()).unwrap_err();
+        assert_eq!(ErrorKind::LimitExceeded, err.kind());
+    }
+}
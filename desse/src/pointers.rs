@@ -0,0 +1,328 @@
+#![cfg(feature = "dynamic")]
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use crate::{DesseBorrowed, DesseDynamic, DesseOwned, LimitedReader, Reader, Result, Writer};
+
+/// Values behind a pointer are serialized in place (pointers themselves are not part of encoding). Each pointer
+/// counts as a level of nesting while deserializing, so that [`Limits::with_max_depth()`](crate::Limits::with_max_depth)
+/// bounds recursion of recursive types.
+macro_rules! impl_desse_dynamic_pointer {
+    ($type: ident) => {
+        impl<T> DesseDynamic for $type<T>
+        where
+            T: DesseDynamic,
+        {
+            type Output = $type<T::Output>;
+
+            const MIN_SERIALIZED_SIZE: usize = T::MIN_SERIALIZED_SIZE;
+
+            #[inline]
+            fn serialized_size(&self) -> usize {
+                DesseDynamic::serialized_size(&**self)
+            }
+
+            #[inline]
+            fn serialize(&self) -> Result<Vec<u8>> {
+                DesseDynamic::serialize(&**self)
+            }
+
+            #[inline]
+            fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+                DesseDynamic::serialize_into(&**self, writer)
+            }
+
+            #[inline]
+            fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+                DesseDynamic::serialize_into_unchecked(&**self, writer)
+            }
+        }
+
+        impl<T> DesseOwned for $type<T>
+        where
+            T: DesseOwned,
+        {
+            #[inline]
+            fn deserialize_owned<R: Reader>(mut reader: R) -> Result<Self::Output> {
+                reader.enter_nested()?;
+                let value = T::deserialize_owned(reader.inner_mut());
+                reader.exit_nested();
+
+                value.map($type::new)
+            }
+
+            #[inline]
+            fn deserialize_owned_unchecked<R: Reader>(mut reader: R) -> Result<Self::Output> {
+                reader.enter_nested()?;
+                let value = T::deserialize_owned_unchecked(reader.inner_mut());
+                reader.exit_nested();
+
+                value.map($type::new)
+            }
+        }
+
+        impl<'de, T> DesseBorrowed<'de> for $type<T>
+        where
+            T: DesseBorrowed<'de>,
+        {
+            #[inline]
+            fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+                reader.enter_nested()?;
+                let value = T::deserialize_borrowed_from(reader);
+                reader.exit_nested();
+
+                value.map($type::new)
+            }
+        }
+    };
+}
+
+impl_desse_dynamic_pointer!(Box);
+impl_desse_dynamic_pointer!(Rc);
+impl_desse_dynamic_pointer!(Arc);
+
+/// Slices are serialized in same way as `Vec`
+impl<T> DesseDynamic for Box<[T]>
+where
+    T: DesseDynamic,
+{
+    type Output = Box<[T::Output]>;
+
+    const MIN_SERIALIZED_SIZE: usize = <&[T]>::MIN_SERIALIZED_SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        DesseDynamic::serialized_size(&&**self)
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        DesseDynamic::serialize(&&**self)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        DesseDynamic::serialize_into(&&**self, writer)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        DesseDynamic::serialize_into_unchecked(&&**self, writer)
+    }
+}
+
+impl<T> DesseOwned for Box<[T]>
+where
+    T: DesseOwned,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        Vec::<T>::deserialize_owned(reader).map(Vec::into_boxed_slice)
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        Vec::<T>::deserialize_owned_unchecked(reader).map(Vec::into_boxed_slice)
+    }
+}
+
+impl<'de, T> DesseBorrowed<'de> for Box<[T]>
+where
+    T: DesseBorrowed<'de>,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        Vec::<T>::deserialize_borrowed_from(reader).map(Vec::into_boxed_slice)
+    }
+}
+
+/// Deserializing a `Cow` always gives an owned value (use [`DesseBorrowed`](crate::DesseBorrowed) for borrowing
+/// strings and byte slices)
+impl<T> DesseDynamic for Cow<'_, T>
+where
+    T: DesseDynamic + Clone,
+{
+    type Output = T::Output;
+
+    const MIN_SERIALIZED_SIZE: usize = T::MIN_SERIALIZED_SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        DesseDynamic::serialized_size(&**self)
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        DesseDynamic::serialize(&**self)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        DesseDynamic::serialize_into(&**self, writer)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        DesseDynamic::serialize_into_unchecked(&**self, writer)
+    }
+}
+
+impl<T> DesseOwned for Cow<'_, T>
+where
+    T: DesseOwned + Clone,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        T::deserialize_owned(reader)
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        T::deserialize_owned_unchecked(reader)
+    }
+}
+
+impl<'de, T> DesseBorrowed<'de> for Cow<'de, T>
+where
+    T: DesseBorrowed<'de> + Clone,
+{
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        T::deserialize_borrowed_from(reader).map(Cow::Owned)
+    }
+}
+
+impl<T> DesseDynamic for Cow<'_, [T]>
+where
+    T: DesseDynamic + Clone,
+{
+    type Output = Vec<T::Output>;
+
+    const MIN_SERIALIZED_SIZE: usize = <&[T]>::MIN_SERIALIZED_SIZE;
+
+    #[inline]
+    fn serialized_size(&self) -> usize {
+        DesseDynamic::serialized_size(&&**self)
+    }
+
+    #[inline]
+    fn serialize(&self) -> Result<Vec<u8>> {
+        DesseDynamic::serialize(&&**self)
+    }
+
+    #[inline]
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        DesseDynamic::serialize_into(&&**self, writer)
+    }
+
+    #[inline]
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        DesseDynamic::serialize_into_unchecked(&&**self, writer)
+    }
+}
+
+impl<T> DesseOwned for Cow<'_, [T]>
+where
+    T: DesseOwned + Clone,
+{
+    #[inline]
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+        Vec::<T>::deserialize_owned(reader)
+    }
+
+    #[inline]
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+        Vec::<T>::deserialize_owned_unchecked(reader)
+    }
+}
+
+impl<'de> DesseBorrowed<'de> for Cow<'de, [u8]> {
+    #[inline]
+    fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+        <&'de [u8]>::deserialize_borrowed_from(reader).map(Cow::Borrowed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // For initializing global memory allocator
+    extern crate std;
+
+    use super::*;
+
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    use crate::{ErrorKind, Limits};
+
+    #[test]
+    fn check_pointers() {
+        let value = Box::new("boxed".to_string());
+        let bytes = DesseDynamic::serialize(&value).unwrap();
+
+        // Same encoding as pointed value
+        assert_eq!(DesseDynamic::serialize(&*value).unwrap(), bytes);
+        assert_eq!(value, Box::<String>::deserialize_exact(&bytes).unwrap());
+        assert_eq!(
+            Rc::new("boxed".to_string()),
+            Rc::<String>::deserialize_exact(&bytes).unwrap()
+        );
+        assert_eq!(
+            Arc::new("boxed"),
+            Arc::<&str>::deserialize_borrowed(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_pointer_depth() {
+        let value = Box::new(Box::new(Some(1u8)));
+        let bytes = DesseDynamic::serialize(&value).unwrap();
+
+        let limits = Limits::new().with_max_depth(3);
+        assert_eq!(
+            value,
+            Box::<Box<Option<u8>>>::deserialize_from_with_limits(&*bytes, &limits).unwrap()
+        );
+
+        let limits = Limits::new().with_max_depth(2);
+        let err =
+            Box::<Box<Option<u8>>>::deserialize_from_with_limits(&*bytes, &limits).unwrap_err();
+        assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+        let err =
+            Box::<Box<Option<u8>>>::deserialize_borrowed_with_limits(&bytes, &limits).unwrap_err();
+        assert_eq!(ErrorKind::LimitExceeded, err.kind());
+    }
+
+    #[test]
+    fn check_boxed_slice() {
+        let value: Box<[u16]> = vec![1, 2, 3].into_boxed_slice();
+        let bytes = DesseDynamic::serialize(&value).unwrap();
+
+        assert_eq!(DesseDynamic::serialize(&vec![1u16, 2, 3]).unwrap(), bytes);
+        assert_eq!(value, Box::<[u16]>::deserialize_exact(&bytes).unwrap());
+        assert_eq!(value, Box::<[u16]>::deserialize_borrowed(&bytes).unwrap());
+    }
+
+    #[test]
+    fn check_cow() {
+        let value: Cow<'_, u32> = Cow::Owned(5);
+        let bytes = DesseDynamic::serialize(&value).unwrap();
+        assert_eq!(5, Cow::<u32>::deserialize_exact(&bytes).unwrap());
+
+        let value: Cow<'_, [u8]> = Cow::Borrowed(&[1, 2, 3]);
+        let bytes = DesseDynamic::serialize(&value).unwrap();
+        assert_eq!(
+            vec![1, 2, 3],
+            Cow::<[u8]>::deserialize_exact(&bytes).unwrap()
+        );
+
+        match Cow::<[u8]>::deserialize_borrowed(&bytes).unwrap() {
+            Cow::Borrowed(slice) => assert_eq!(&[1, 2, 3], slice),
+            Cow::Owned(_) => panic!("Expected borrowed slice"),
+        }
+    }
+}
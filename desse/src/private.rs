@@ -7,4 +7,4 @@ impl Sealed for &[u8] {}
 impl Sealed for &mut [u8] {}
 impl Sealed for Vec<u8> {}
 
-impl<S> Sealed for &mut S where S: Sealed {}
+impl<S> Sealed for &mut S where S: Sealed + ?Sized {}
@@ -4,6 +4,17 @@ use crate::{ErrorKind, Result};
 
 /// Trait for doing `read` operations.
 pub trait Reader: Sealed + Sized {
+    /// Reader to which current reader forwards its operations (`Self` for readers which are not references)
+    #[doc(hidden)]
+    type Inner: Reader;
+
+    /// Returns reader to which current reader forwards its operations
+    ///
+    /// Nested values are deserialized from this reader instead of `&mut self` so that deserializing a recursive type
+    /// does not instantiate an unbounded chain of `&mut &mut ...` readers.
+    #[doc(hidden)]
+    fn inner_mut(&mut self) -> &mut Self::Inner;
+
     /// Reads `len` bytes from current reader
     fn read(&mut self, len: usize) -> Result<&[u8]>;
 
@@ -13,9 +24,52 @@ pub trait Reader: Sealed + Sized {
     ///
     /// Panics if there are not enough bytes in reader
     fn read_unchecked(&mut self, len: usize) -> Result<&[u8]>;
+
+    /// Returns `true` if there are no more bytes to read from current reader
+    fn is_at_end(&mut self) -> Result<bool>;
+
+    /// Checks that at least `len` more bytes can be read from current reader (without consuming them). Readers which
+    /// cannot tell the number of remaining bytes in advance always return `Ok(())`.
+    #[inline]
+    fn ensure(&mut self, len: usize) -> Result<()> {
+        let _ = len;
+        Ok(())
+    }
+
+    /// Checks number of elements of a sequence against limits of current reader (if any) before it is allocated
+    #[inline]
+    fn check_sequence_len(&mut self, len: u64) -> Result<()> {
+        let _ = len;
+        Ok(())
+    }
+
+    /// Checks length (in bytes) of a string against limits of current reader (if any) before it is allocated
+    #[inline]
+    fn check_string_len(&mut self, len: u64) -> Result<()> {
+        let _ = len;
+        Ok(())
+    }
+
+    /// Marks start of a nested container, checking nesting depth against limits of current reader (if any). Every
+    /// successful call must be paired with a call to `exit_nested()`.
+    #[inline]
+    fn enter_nested(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Marks end of a nested container started with `enter_nested()`
+    #[inline]
+    fn exit_nested(&mut self) {}
 }
 
 impl Reader for &[u8] {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
     #[inline]
     fn read(&mut self, len: usize) -> Result<&[u8]> {
         if self.len() < len {
@@ -31,12 +85,33 @@ impl Reader for &[u8] {
         *self = b;
         Ok(a)
     }
+
+    #[inline]
+    fn is_at_end(&mut self) -> Result<bool> {
+        Ok(self.is_empty())
+    }
+
+    #[inline]
+    fn ensure(&mut self, len: usize) -> Result<()> {
+        if self.len() < len {
+            return Err(ErrorKind::InvalidSliceLength.into());
+        }
+
+        Ok(())
+    }
 }
 
 impl<R> Reader for &mut R
 where
     R: Reader,
 {
+    type Inner = R;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
     #[inline]
     fn read(&mut self, len: usize) -> Result<&[u8]> {
         (**self).read(len)
@@ -46,4 +121,34 @@ where
     fn read_unchecked(&mut self, len: usize) -> Result<&[u8]> {
         (**self).read_unchecked(len)
     }
+
+    #[inline]
+    fn is_at_end(&mut self) -> Result<bool> {
+        (**self).is_at_end()
+    }
+
+    #[inline]
+    fn ensure(&mut self, len: usize) -> Result<()> {
+        (**self).ensure(len)
+    }
+
+    #[inline]
+    fn check_sequence_len(&mut self, len: u64) -> Result<()> {
+        (**self).check_sequence_len(len)
+    }
+
+    #[inline]
+    fn check_string_len(&mut self, len: u64) -> Result<()> {
+        (**self).check_string_len(len)
+    }
+
+    #[inline]
+    fn enter_nested(&mut self) -> Result<()> {
+        (**self).enter_nested()
+    }
+
+    #[inline]
+    fn exit_nested(&mut self) {
+        (**self).exit_nested()
+    }
 }
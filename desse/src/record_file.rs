@@ -0,0 +1,428 @@
+#![cfg(feature = "std")]
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::record_slice::deserialize_record;
+use crate::{crc32c, DesseSized, DesseStatic, ErrorKind, Result};
+
+/// Magic bytes at the start of a record file
+const MAGIC: [u8; 8] = *b"DESSEREC";
+
+/// Size of header of a record file (magic, record size and schema hash)
+const HEADER_SIZE: u64 = (MAGIC.len() + <u64>::SIZE + <u64>::SIZE) as u64;
+
+/// File of fixed size [`DesseStatic`](crate::DesseStatic) records with random access
+///
+/// File starts with a header containing magic bytes, `T::SIZE` and a schema hash, followed by back-to-back serialized
+/// records. A file can only be opened as `RecordFile<T>` if its header matches `T`.
+///
+/// Unless given explicitly (`create_with_schema()` and `open_with_schema()`), schema hash is a checksum of type name of
+/// `T` (see [`type_name()`](core::any::type_name)), so that a file cannot be opened as a different type of same size.
+/// Type name is not guaranteed to be stable across compiler versions and changes when `T` is renamed or moved to a
+/// different module. So, use an explicit schema hash for files which should outlive these.
+///
+/// # Panic
+///
+/// All the constructors panic if `T::SIZE` is zero.
+///
+/// # Example
+///
+/// ```
+/// use desse::RecordFile;
+///
+/// let path = std::env::temp_dir().join(format!("desse-doc-{}.rec", std::process::id()));
+///
+/// let mut file = RecordFile::<u32>::create(&path).unwrap();
+/// file.append(&1).unwrap();
+/// file.append(&2).unwrap();
+/// file.set(0, &3).unwrap();
+///
+/// let mut file = RecordFile::<u32>::open(&path).unwrap();
+/// assert_eq!(2, file.len());
+/// assert_eq!(3, file.get(0).unwrap());
+///
+/// std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct RecordFile<T> {
+    file: File,
+    len: u64,
+    buf: Vec<u8>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RecordFile<T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>,
+{
+    /// Creates a new empty record file (truncating existing file, if any) with schema hash derived from type name of
+    /// `T`
+    #[inline]
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::create_with_schema(path, Self::type_hash())
+    }
+
+    /// Creates a new empty record file (truncating existing file, if any) with given schema hash
+    pub fn create_with_schema<P: AsRef<Path>>(path: P, schema_hash: u64) -> Result<Self> {
+        let header = Self::header(schema_hash);
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+
+        file.write_all(&header)?;
+
+        Ok(Self::new(file, 0))
+    }
+
+    /// Opens an existing record file created by `create()`
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::HeaderMismatch`) when header of file does not match `T`.
+    #[inline]
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::open_with_schema(path, Self::type_hash())
+    }
+
+    /// Opens an existing record file with given schema hash
+    ///
+    /// If the file ends with a partially written record (e.g., due to an interrupted `append()`), it is ignored and
+    /// overwritten by next `append()`.
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::HeaderMismatch`) when header of file does not match `T` or
+    /// `schema_hash`.
+    pub fn open_with_schema<P: AsRef<Path>>(path: P, schema_hash: u64) -> Result<Self> {
+        let expected = Self::header(schema_hash);
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+
+        let mut header = [0; HEADER_SIZE as usize];
+
+        match file.read_exact(&mut header) {
+            Err(ref err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                return Err(ErrorKind::HeaderMismatch.into())
+            }
+            result => result?,
+        }
+
+        if header != expected {
+            return Err(ErrorKind::HeaderMismatch.into());
+        }
+
+        let len = (file.metadata()?.len() - HEADER_SIZE) / T::SIZE as u64;
+
+        Ok(Self::new(file, len))
+    }
+
+    /// Returns number of records in file
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if there are no records in file
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends a record at the end of file and returns its index
+    pub fn append(&mut self, record: &T) -> Result<u64> {
+        let index = self.len;
+
+        self.file.seek(SeekFrom::Start(Self::offset(index)))?;
+        self.file.write_all(record.serialize().as_ref())?;
+        self.len += 1;
+
+        Ok(index)
+    }
+
+    /// Returns record at given index
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::IndexOutOfBounds`) when `index` is not less than `len()`.
+    pub fn get(&mut self, index: u64) -> Result<T> {
+        self.check_index(index)?;
+
+        self.file.seek(SeekFrom::Start(Self::offset(index)))?;
+        self.file.read_exact(&mut self.buf)?;
+
+        deserialize_record(&self.buf)
+    }
+
+    /// Replaces record at given index
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::IndexOutOfBounds`) when `index` is not less than `len()`.
+    pub fn set(&mut self, index: u64, record: &T) -> Result<()> {
+        self.check_index(index)?;
+
+        self.file.seek(SeekFrom::Start(Self::offset(index)))?;
+        self.file.write_all(record.serialize().as_ref())?;
+
+        Ok(())
+    }
+
+    /// Returns an iterator over all the records in file
+    pub fn iter(&mut self) -> Result<RecordFileIter<'_, T>> {
+        self.file.seek(SeekFrom::Start(HEADER_SIZE))?;
+
+        Ok(RecordFileIter {
+            reader: BufReader::new(&self.file),
+            remaining: self.len,
+            buf: &mut self.buf,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Flushes all the written records to disk
+    #[inline]
+    pub fn sync_all(&self) -> Result<()> {
+        Ok(self.file.sync_all()?)
+    }
+
+    /// Returns underlying file
+    #[inline]
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+
+    #[inline]
+    fn new(file: File, len: u64) -> Self {
+        Self {
+            file,
+            len,
+            buf: vec![0; T::SIZE],
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns default schema hash of `T` (checksum of its type name)
+    #[inline]
+    fn type_hash() -> u64 {
+        u64::from(crc32c(core::any::type_name::<T>().as_bytes()))
+    }
+
+    #[inline]
+    fn header(schema_hash: u64) -> [u8; HEADER_SIZE as usize] {
+        assert_ne!(
+            0,
+            T::SIZE,
+            "Records of zero size cannot be stored in a file"
+        );
+
+        let mut header = [0; HEADER_SIZE as usize];
+
+        header[..8].copy_from_slice(&MAGIC);
+        header[8..16].copy_from_slice(&(T::SIZE as u64).to_le_bytes());
+        header[16..].copy_from_slice(&schema_hash.to_le_bytes());
+
+        header
+    }
+
+    #[inline]
+    fn offset(index: u64) -> u64 {
+        HEADER_SIZE + index * T::SIZE as u64
+    }
+
+    #[inline]
+    fn check_index(&self, index: u64) -> Result<()> {
+        if index >= self.len {
+            return Err(ErrorKind::IndexOutOfBounds.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Iterator over records of a [`RecordFile`](self::RecordFile)
+#[derive(Debug)]
+pub struct RecordFileIter<'a, T> {
+    reader: BufReader<&'a File>,
+    remaining: u64,
+    buf: &'a mut Vec<u8>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Iterator for RecordFileIter<'_, T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        if let Err(err) = self.reader.read_exact(self.buf) {
+            self.remaining = 0;
+            return Some(Err(err.into()));
+        }
+
+        Some(deserialize_record(self.buf))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for RecordFileIter<'_, T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    /// Temporary file path which is removed on drop
+    struct TempPath(PathBuf);
+
+    impl TempPath {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(std::format!(
+                "desse-{}-{}.rec",
+                name,
+                std::process::id()
+            )))
+        }
+    }
+
+    impl Drop for TempPath {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn check_record_file() {
+        let path = TempPath::new("record-file");
+
+        let mut file = RecordFile::<u16>::create(&path.0).unwrap();
+        assert!(file.is_empty());
+
+        for i in 0..100u16 {
+            assert_eq!(u64::from(i), file.append(&(i * 2)).unwrap());
+        }
+
+        file.set(10, &1000).unwrap();
+        assert_eq!(100, file.len());
+        assert_eq!(1000, file.get(10).unwrap());
+        assert_eq!(198, file.get(99).unwrap());
+
+        assert_eq!(
+            ErrorKind::IndexOutOfBounds,
+            file.get(100).unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::IndexOutOfBounds,
+            file.set(100, &0).unwrap_err().kind()
+        );
+
+        drop(file);
+
+        let mut file = RecordFile::<u16>::open(&path.0).unwrap();
+        assert_eq!(100, file.len());
+
+        let iter = file.iter().unwrap();
+        assert_eq!(100, iter.len());
+
+        let records = iter.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(1000, records[10]);
+        assert_eq!(
+            (0..100u16)
+                .map(|i| if i == 10 { 1000 } else { i * 2 })
+                .collect::<Vec<_>>(),
+            records
+        );
+
+        assert_eq!(8, file.get(4).unwrap());
+    }
+
+    #[test]
+    fn check_header_mismatch() {
+        let path = TempPath::new("header-mismatch");
+
+        RecordFile::<u32>::create_with_schema(&path.0, 42).unwrap();
+
+        assert!(RecordFile::<u32>::open_with_schema(&path.0, 42).is_ok());
+        assert_eq!(
+            ErrorKind::HeaderMismatch,
+            RecordFile::<u32>::open(&path.0).unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::HeaderMismatch,
+            RecordFile::<u64>::open_with_schema(&path.0, 42)
+                .unwrap_err()
+                .kind()
+        );
+
+        // Types of same size are distinguished by default
+        RecordFile::<u32>::create(&path.0).unwrap();
+
+        assert!(RecordFile::<u32>::open(&path.0).is_ok());
+        assert_eq!(
+            ErrorKind::HeaderMismatch,
+            RecordFile::<i32>::open(&path.0).unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::HeaderMismatch,
+            RecordFile::<[u8; 4]>::open(&path.0).unwrap_err().kind()
+        );
+
+        std::fs::write(&path.0, b"DESSE").unwrap();
+        assert_eq!(
+            ErrorKind::HeaderMismatch,
+            RecordFile::<u32>::open(&path.0).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn check_partial_record() {
+        let path = TempPath::new("partial-record");
+
+        let mut file = RecordFile::<u32>::create(&path.0).unwrap();
+        file.append(&1).unwrap();
+        file.into_inner().write_all(&[1, 2]).unwrap();
+
+        let mut file = RecordFile::<u32>::open(&path.0).unwrap();
+        assert_eq!(1, file.len());
+
+        file.append(&2).unwrap();
+        drop(file);
+
+        assert_eq!(HEADER_SIZE + 8, std::fs::metadata(&path.0).unwrap().len());
+        assert_eq!(
+            vec![1, 2],
+            RecordFile::<u32>::open(&path.0)
+                .unwrap()
+                .iter()
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,561 @@
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+use core::slice::Chunks;
+
+use crate::{
+    iter_static, DesseSized, DesseStatic, ErrorKind, Field, OrderPreserving, Result, StaticIter,
+};
+
+/// Deserializes a record from a slice of exactly `T::SIZE` bytes
+#[inline]
+pub(crate) fn deserialize_record<T>(bytes: &[u8]) -> Result<T>
+where
+    T: DesseStatic,
+    T::Output: for<'a> TryFrom<&'a [u8]>,
+{
+    match T::Output::try_from(bytes) {
+        Ok(bytes) => T::deserialize_from(&bytes),
+        Err(_) => Err(ErrorKind::InvalidSliceLength.into()),
+    }
+}
+
+/// Checks that `bytes` contain a whole number of records. Panics if `T::SIZE` is zero.
+// `usize::is_multiple_of()` needs a much newer compiler than rest of the crate
+#[allow(clippy::manual_is_multiple_of)]
+#[inline]
+fn check_len<T: DesseSized>(bytes: &[u8]) -> Result<()> {
+    assert_ne!(0, T::SIZE, "Records of zero size cannot be indexed");
+
+    if bytes.len() % T::SIZE != 0 {
+        return Err(ErrorKind::InvalidSliceLength.into());
+    }
+
+    Ok(())
+}
+
+/// Typed view of back-to-back [`DesseStatic`](crate::DesseStatic) records in borrowed bytes
+///
+/// Records are deserialized only when accessed.
+///
+/// # Example
+///
+/// ```
+/// use desse::{DesseStatic, RecordSlice};
+///
+/// let mut bytes = [0; 6];
+/// bytes[..2].copy_from_slice(&1u16.serialize());
+/// bytes[2..4].copy_from_slice(&2u16.serialize());
+/// bytes[4..].copy_from_slice(&3u16.serialize());
+///
+/// let records = RecordSlice::<u16>::new(&bytes).unwrap();
+/// assert_eq!(3, records.len());
+/// assert_eq!(2, records.get(1).unwrap());
+///
+/// let (left, right) = records.split_at(1);
+/// assert_eq!(vec![2, 3], right.iter().collect::<Result<Vec<_>, _>>().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct RecordSlice<'a, T> {
+    bytes: &'a [u8],
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for RecordSlice<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RecordSlice<'_, T> {}
+
+impl<'a, T> RecordSlice<'a, T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: for<'b> TryFrom<&'b [u8]>,
+{
+    /// Creates a new record slice
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::InvalidSliceLength`) when length of `bytes` is not a multiple of
+    /// `T::SIZE`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `T::SIZE` is zero.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        check_len::<T>(bytes)?;
+
+        Ok(Self {
+            bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns number of records
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len() / T::SIZE
+    }
+
+    /// Returns `true` if there are no records
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns underlying bytes
+    #[inline]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns serialized bytes of record at given index (if any)
+    #[inline]
+    pub fn get_bytes(&self, index: usize) -> Option<&'a [u8]> {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(&self.bytes[(index * T::SIZE)..((index + 1) * T::SIZE)])
+    }
+
+    /// Deserializes record at given index
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::IndexOutOfBounds`) when `index` is not less than `len()`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Result<T> {
+        match self.get_bytes(index) {
+            None => Err(ErrorKind::IndexOutOfBounds.into()),
+            Some(bytes) => deserialize_record(bytes),
+        }
+    }
+
+    /// Returns an iterator over all the records
+    #[inline]
+    pub fn iter(&self) -> StaticIter<'a, T> {
+        iter_static(self.bytes)
+    }
+
+    /// Divides current slice into two at given record index
+    ///
+    /// # Panic
+    ///
+    /// Panics if `mid > len()`.
+    #[inline]
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        assert!(mid <= self.len(), "Index of record is out of bounds");
+
+        let (left, right) = self.bytes.split_at(mid * T::SIZE);
+
+        (
+            Self {
+                bytes: left,
+                _marker: PhantomData,
+            },
+            Self {
+                bytes: right,
+                _marker: PhantomData,
+            },
+        )
+    }
+
+    /// Returns an iterator over slices of `chunk_len` records (last slice may be shorter)
+    ///
+    /// # Panic
+    ///
+    /// Panics if `chunk_len` is zero.
+    #[inline]
+    pub fn chunks(&self, chunk_len: usize) -> RecordChunks<'a, T> {
+        assert_ne!(0, chunk_len, "Chunk length cannot be zero");
+
+        RecordChunks {
+            chunks: self.bytes.chunks(chunk_len * T::SIZE),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Binary searches records (sorted by `field`) for given `key` by comparing serialized bytes of field, without
+    /// deserializing any record
+    ///
+    /// Like [`slice::binary_search()`](https://doc.rust-lang.org/std/primitive.slice.html#method.binary_search),
+    /// returns `Ok` with index of a matching record (any one of them if there are multiple matches) or `Err` with the
+    /// index where a record with `key` could be inserted while maintaining sorted order. Result is unspecified if
+    /// records are not sorted by `field`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use desse::{DesseSized, DesseStatic, RecordSlice};
+    ///
+    /// #[derive(Debug, PartialEq, DesseStatic, DesseSized)]
+    /// #[desse(field_consts)]
+    /// struct Entry {
+    ///     id: u32,
+    ///     value: i16,
+    /// }
+    ///
+    /// let mut bytes = Vec::new();
+    ///
+    /// for id in &[2, 3, 5, 7] {
+    ///     bytes.extend(&Entry { id: *id, value: 0 }.serialize());
+    /// }
+    ///
+    /// let records = RecordSlice::<Entry>::new(&bytes).unwrap();
+    ///
+    /// assert_eq!(Ok(2), records.binary_search_by_field(Entry::FIELD_ID, &5));
+    /// assert_eq!(Err(3), records.binary_search_by_field(Entry::FIELD_ID, &6));
+    /// ```
+    pub fn binary_search_by_field<F>(
+        &self,
+        field: Field<T, F>,
+        key: &F,
+    ) -> core::result::Result<usize, usize>
+    where
+        F: OrderPreserving,
+        F::Output: AsRef<[u8]>,
+    {
+        let key = key.serialize();
+        let key = key.as_ref();
+
+        let mut low = 0;
+        let mut high = self.len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let record = &self.bytes[(mid * T::SIZE)..((mid + 1) * T::SIZE)];
+
+            match F::cmp_serialized(field.bytes(record), key) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(mid),
+            }
+        }
+
+        Err(low)
+    }
+}
+
+/// Iterator over chunks of a [`RecordSlice`](self::RecordSlice)
+#[derive(Debug)]
+pub struct RecordChunks<'a, T> {
+    chunks: Chunks<'a, u8>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Iterator for RecordChunks<'a, T> {
+    type Item = RecordSlice<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.chunks.next().map(|bytes| RecordSlice {
+            bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for RecordChunks<'_, T> {}
+
+/// Mutable typed view of back-to-back [`DesseStatic`](crate::DesseStatic) records in borrowed bytes
+///
+/// # Example
+///
+/// ```
+/// use desse::RecordSliceMut;
+///
+/// let mut bytes = [0; 8];
+/// let mut records = RecordSliceMut::<u32>::new(&mut bytes).unwrap();
+///
+/// records.set(0, &7).unwrap();
+/// records.swap(0, 1).unwrap();
+///
+/// assert_eq!(0, records.get(0).unwrap());
+/// assert_eq!(7, records.get(1).unwrap());
+/// ```
+#[derive(Debug)]
+pub struct RecordSliceMut<'a, T> {
+    bytes: &'a mut [u8],
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> RecordSliceMut<'a, T>
+where
+    T: DesseStatic + DesseSized,
+    T::Output: AsRef<[u8]> + for<'b> TryFrom<&'b [u8]>,
+{
+    /// Creates a new mutable record slice
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::InvalidSliceLength`) when length of `bytes` is not a multiple of
+    /// `T::SIZE`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `T::SIZE` is zero.
+    #[inline]
+    pub fn new(bytes: &'a mut [u8]) -> Result<Self> {
+        check_len::<T>(bytes)?;
+
+        Ok(Self {
+            bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns number of records
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len() / T::SIZE
+    }
+
+    /// Returns `true` if there are no records
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns an immutable view of current slice
+    #[inline]
+    pub fn as_slice(&self) -> RecordSlice<'_, T> {
+        RecordSlice {
+            bytes: self.bytes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Deserializes record at given index
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::IndexOutOfBounds`) when `index` is not less than `len()`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Result<T> {
+        self.as_slice().get(index)
+    }
+
+    /// Serializes `record` at given index
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::IndexOutOfBounds`) when `index` is not less than `len()`.
+    #[inline]
+    pub fn set(&mut self, index: usize, record: &T) -> Result<()> {
+        self.check_index(index)?;
+
+        self.bytes[(index * T::SIZE)..((index + 1) * T::SIZE)]
+            .copy_from_slice(record.serialize().as_ref());
+
+        Ok(())
+    }
+
+    /// Swaps records at given indices (without deserializing them)
+    ///
+    /// # Error
+    ///
+    /// This function returns error (`ErrorKind::IndexOutOfBounds`) when any of the indices is not less than `len()`.
+    pub fn swap(&mut self, a: usize, b: usize) -> Result<()> {
+        self.check_index(a)?;
+        self.check_index(b)?;
+
+        self.swap_unchecked(a, b);
+
+        Ok(())
+    }
+
+    /// Sorts records in place by `field`, comparing serialized bytes of field without deserializing any record
+    ///
+    /// Sort is unstable (i.e., may reorder records with equal fields), in place (i.e., does not allocate) and
+    /// `O(n * log(n))` in worst case.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use desse::{DesseSized, DesseStatic, RecordSliceMut};
+    ///
+    /// #[derive(Debug, PartialEq, DesseStatic, DesseSized)]
+    /// #[desse(field_consts)]
+    /// struct Entry(u8, i64);
+    ///
+    /// let mut bytes = Vec::new();
+    ///
+    /// for value in &[3, -1, 2] {
+    ///     bytes.extend(&Entry(0, *value).serialize());
+    /// }
+    ///
+    /// let mut records = RecordSliceMut::<Entry>::new(&mut bytes).unwrap();
+    /// records.sort_by_field(Entry::FIELD_1);
+    ///
+    /// assert_eq!(Entry(0, -1), records.get(0).unwrap());
+    /// assert_eq!(Entry(0, 3), records.get(2).unwrap());
+    /// ```
+    pub fn sort_by_field<F>(&mut self, field: Field<T, F>)
+    where
+        F: OrderPreserving,
+    {
+        // Heapsort, as records can only be moved by swapping their bytes
+        let len = self.len();
+
+        for root in (0..(len / 2)).rev() {
+            self.sift_down(field, root, len);
+        }
+
+        for end in (1..len).rev() {
+            self.swap_unchecked(0, end);
+            self.sift_down(field, 0, end);
+        }
+    }
+
+    /// Moves record at `root` down the max-heap formed by first `end` records
+    fn sift_down<F: OrderPreserving>(&mut self, field: Field<T, F>, mut root: usize, end: usize) {
+        loop {
+            let mut child = 2 * root + 1;
+
+            if child >= end {
+                return;
+            }
+
+            if child + 1 < end && self.cmp_by_field(field, child, child + 1) == Ordering::Less {
+                child += 1;
+            }
+
+            if self.cmp_by_field(field, root, child) != Ordering::Less {
+                return;
+            }
+
+            self.swap_unchecked(root, child);
+            root = child;
+        }
+    }
+
+    #[inline]
+    fn cmp_by_field<F: OrderPreserving>(&self, field: Field<T, F>, a: usize, b: usize) -> Ordering {
+        F::cmp_serialized(
+            field.bytes(&self.bytes[(a * T::SIZE)..((a + 1) * T::SIZE)]),
+            field.bytes(&self.bytes[(b * T::SIZE)..((b + 1) * T::SIZE)]),
+        )
+    }
+
+    /// Swaps records at given indices (which must be less than `len()`)
+    #[inline]
+    fn swap_unchecked(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+        let (left, right) = self.bytes.split_at_mut(high * T::SIZE);
+
+        left[(low * T::SIZE)..((low + 1) * T::SIZE)].swap_with_slice(&mut right[..T::SIZE]);
+    }
+
+    #[inline]
+    fn check_index(&self, index: usize) -> Result<()> {
+        if index >= self.len() {
+            return Err(ErrorKind::IndexOutOfBounds.into());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // For initializing global memory allocator
+    extern crate std;
+
+    use super::*;
+
+    fn records() -> [u8; 5 * 4] {
+        let mut bytes = [0; 5 * 4];
+
+        for (i, chunk) in bytes.chunks_mut(4).enumerate() {
+            chunk.copy_from_slice(&(i as u32 * 10).to_le_bytes());
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn check_record_slice() {
+        let bytes = records();
+        let records = RecordSlice::<u32>::new(&bytes).unwrap();
+
+        assert_eq!(5, records.len());
+        assert!(!records.is_empty());
+        assert_eq!(30, records.get(3).unwrap());
+        assert_eq!(Some(&bytes[4..8]), records.get_bytes(1));
+        assert_eq!(
+            ErrorKind::IndexOutOfBounds,
+            records.get(5).unwrap_err().kind()
+        );
+
+        let mut iter = records.iter();
+        assert_eq!(5, iter.len());
+        assert_eq!(0, iter.next().unwrap().unwrap());
+        assert_eq!(40, iter.last().unwrap().unwrap());
+
+        assert_eq!(
+            ErrorKind::InvalidSliceLength,
+            RecordSlice::<u32>::new(&bytes[1..]).unwrap_err().kind()
+        );
+        assert!(RecordSlice::<u32>::new(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn check_split_at_and_chunks() {
+        let bytes = records();
+        let records = RecordSlice::<u32>::new(&bytes).unwrap();
+
+        let (left, right) = records.split_at(2);
+        assert_eq!(2, left.len());
+        assert_eq!(3, right.len());
+        assert_eq!(20, right.get(0).unwrap());
+
+        let (left, right) = records.split_at(5);
+        assert_eq!(5, left.len());
+        assert!(right.is_empty());
+
+        let chunks = records.chunks(2);
+        assert_eq!(3, chunks.len());
+
+        let lens = chunks
+            .map(|chunk| chunk.len())
+            .collect::<std::vec::Vec<_>>();
+        assert_eq!([2, 2, 1], *lens);
+    }
+
+    #[test]
+    fn check_record_slice_mut() {
+        let mut bytes = records();
+        let mut records = RecordSliceMut::<u32>::new(&mut bytes).unwrap();
+
+        records.set(1, &11).unwrap();
+        records.swap(1, 4).unwrap();
+        records.swap(2, 2).unwrap();
+
+        assert_eq!(40, records.get(1).unwrap());
+        assert_eq!(11, records.get(4).unwrap());
+        assert_eq!(20, records.as_slice().get(2).unwrap());
+
+        assert_eq!(
+            ErrorKind::IndexOutOfBounds,
+            records.set(5, &0).unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::IndexOutOfBounds,
+            records.swap(0, 5).unwrap_err().kind()
+        );
+    }
+}
@@ -0,0 +1,265 @@
+#![cfg(feature = "dynamic")]
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+use crate::{DesseDynamic, DesseOwned, DesseSized, ErrorKind, Reader, Result, Writer};
+
+/// Number of serialized bytes after which a chunk is written by
+/// [`serialize_iter_chunked()`](self::serialize_iter_chunked)
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Trait for writers which can back-patch the length of a sequence after writing its items
+pub trait SeekableWriter: Writer {
+    /// Writes a `u64` count followed by the items written by `write_items`. Count is replaced by the value returned by
+    /// `write_items` once all the items are written.
+    fn write_counted(
+        &mut self,
+        write_items: &mut dyn FnMut(&mut dyn Writer) -> Result<u64>,
+    ) -> Result<()>;
+}
+
+impl SeekableWriter for Vec<u8> {
+    fn write_counted(
+        &mut self,
+        write_items: &mut dyn FnMut(&mut dyn Writer) -> Result<u64>,
+    ) -> Result<()> {
+        let position = self.len();
+        self.extend_from_slice(&[0; <u64>::SIZE]);
+
+        match write_items(self) {
+            Ok(count) => {
+                self[position..(position + <u64>::SIZE)].copy_from_slice(&count.to_le_bytes());
+                Ok(())
+            }
+            Err(err) => {
+                self.truncate(position);
+                Err(err)
+            }
+        }
+    }
+}
+
+impl SeekableWriter for &mut [u8] {
+    fn write_counted(
+        &mut self,
+        write_items: &mut dyn FnMut(&mut dyn Writer) -> Result<u64>,
+    ) -> Result<()> {
+        if self.len() < <u64>::SIZE {
+            return Err(ErrorKind::InvalidSliceLength.into());
+        }
+
+        // Slice is advanced only after all the items are written, so that it is left unchanged on error (like
+        // `Vec<u8>` is truncated back)
+        let bytes = core::mem::take(self);
+        let mut items = &mut bytes[<u64>::SIZE..];
+
+        let (count, remaining) = match write_items(&mut items) {
+            Ok(count) => (count, items.len()),
+            Err(err) => {
+                *self = bytes;
+                return Err(err);
+            }
+        };
+
+        let (count_bytes, rest) = bytes.split_at_mut(<u64>::SIZE);
+        count_bytes.copy_from_slice(&count.to_le_bytes());
+
+        let written = rest.len() - remaining;
+        *self = &mut rest[written..];
+
+        Ok(())
+    }
+}
+
+impl<W> SeekableWriter for &mut W
+where
+    W: SeekableWriter + ?Sized,
+{
+    #[inline]
+    fn write_counted(
+        &mut self,
+        write_items: &mut dyn FnMut(&mut dyn Writer) -> Result<u64>,
+    ) -> Result<()> {
+        (**self).write_counted(write_items)
+    }
+}
+
+/// Serializes items of `iter` as they are produced. Output is identical to serialization of a `Vec` of the same
+/// items, i.e., it can be deserialized using `Vec::<T>::deserialize_from()`.
+///
+/// Length of the sequence is back-patched after writing all the items. Use
+/// [`serialize_iter_chunked()`](self::serialize_iter_chunked) for writers which cannot be back-patched.
+///
+/// # Example
+///
+/// ```
+/// use desse::DesseDynamic;
+///
+/// let mut bytes = Vec::new();
+/// desse::serialize_iter(&mut bytes, (1..4u32).filter(|n| n % 2 == 1)).unwrap();
+///
+/// assert_eq!(vec![1, 3], Vec::<u32>::deserialize_from(&*bytes).unwrap());
+/// ```
+pub fn serialize_iter<I, W>(mut writer: W, iter: I) -> Result<()>
+where
+    I: IntoIterator,
+    I::Item: DesseDynamic,
+    W: SeekableWriter,
+{
+    let mut iter = iter.into_iter();
+
+    writer.write_counted(&mut |writer| {
+        let mut count = 0u64;
+
+        for item in &mut iter {
+            DesseDynamic::serialize_into(&item, &mut *writer)?;
+            count += 1;
+        }
+
+        Ok(count)
+    })
+}
+
+/// Serializes items of `iter` as they are produced using a chunked encoding (for writers which cannot be
+/// back-patched). Output can be deserialized using [`deserialize_chunked()`](self::deserialize_chunked).
+///
+/// Items are buffered and written in chunks: number of items in chunk (encoded as `u64`) followed by the items. End of
+/// sequence is marked by an empty chunk.
+pub fn serialize_iter_chunked<I, W>(mut writer: W, iter: I) -> Result<()>
+where
+    I: IntoIterator,
+    I::Item: DesseDynamic,
+    W: Writer,
+{
+    let mut chunk = Vec::new();
+    let mut count = 0u64;
+
+    for item in iter {
+        DesseDynamic::serialize_into(&item, &mut chunk)?;
+        count += 1;
+
+        if chunk.len() >= CHUNK_SIZE {
+            DesseDynamic::serialize_into(&count, &mut writer)?;
+            writer.write(&chunk)?;
+
+            chunk.clear();
+            count = 0;
+        }
+    }
+
+    if count != 0 {
+        DesseDynamic::serialize_into(&count, &mut writer)?;
+        writer.write(&chunk)?;
+    }
+
+    DesseDynamic::serialize_into(&0u64, &mut writer)
+}
+
+/// Deserializes a sequence written by [`serialize_iter_chunked()`](self::serialize_iter_chunked)
+pub fn deserialize_chunked<T, R>(mut reader: R) -> Result<Vec<T::Output>>
+where
+    T: DesseOwned,
+    R: Reader,
+{
+    let mut output = Vec::new();
+
+    loop {
+        let count = <u64 as DesseOwned>::deserialize_owned(&mut reader)?;
+
+        if count == 0 {
+            return Ok(output);
+        }
+
+        reader.ensure(
+            usize::try_from(count)
+                .unwrap_or(usize::MAX)
+                .saturating_mul(T::MIN_SERIALIZED_SIZE),
+        )?;
+
+        let mut i = 0;
+        while i < count {
+            output.push(T::deserialize_from(&mut reader)?);
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::String;
+    use alloc::vec;
+
+    #[test]
+    fn check_serialize_iter_vec() {
+        let words = ["hello", "", "world"];
+
+        let mut bytes = vec![0xAB];
+        serialize_iter(&mut bytes, words.iter().copied()).unwrap();
+
+        assert_eq!(0xAB, bytes[0]);
+        assert_eq!(
+            DesseDynamic::serialize(&words.to_vec()).unwrap(),
+            bytes[1..]
+        );
+    }
+
+    #[test]
+    fn check_serialize_iter_slice() {
+        let mut bytes = [0; 8 + 3 * 2 + 1];
+        let mut writer = &mut bytes[..];
+
+        serialize_iter(&mut writer, (1..=3u16).rev()).unwrap();
+        assert_eq!(1, writer.len());
+
+        assert_eq!(
+            vec![3, 2, 1],
+            Vec::<u16>::deserialize_from(&bytes[..]).unwrap()
+        );
+
+        let mut bytes = [0; 8 + 3];
+        let mut writer = &mut bytes[..];
+
+        let err = serialize_iter(&mut writer, 1..=2u16).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+        // Writer is not advanced past partially written items
+        assert_eq!(8 + 3, writer.len());
+
+        let err = serialize_iter(&mut bytes[..4], 1..=2u16).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+    }
+
+    #[test]
+    fn check_serialize_iter_empty() {
+        let mut bytes = Vec::new();
+        serialize_iter(&mut bytes, core::iter::empty::<u8>()).unwrap();
+
+        assert_eq!(DesseDynamic::serialize(&Vec::<u8>::new()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn check_serialize_iter_chunked() {
+        let count = 3 * CHUNK_SIZE / 4;
+
+        let mut bytes = Vec::new();
+        serialize_iter_chunked(&mut bytes, (0..count).map(|n| n as u32)).unwrap();
+
+        // 3 chunks (2 full and 1 partial) and an empty chunk at end
+        assert_eq!(4 * count + 4 * 8, bytes.len());
+
+        let mut reader = &*bytes;
+        let values = deserialize_chunked::<u32, _>(&mut reader).unwrap();
+
+        assert!(reader.is_empty());
+        assert_eq!((0..count).map(|n| n as u32).collect::<Vec<_>>(), values);
+
+        let mut bytes = Vec::new();
+        serialize_iter_chunked(&mut bytes, core::iter::empty::<String>()).unwrap();
+
+        assert_eq!([0; 8], *bytes);
+        assert!(deserialize_chunked::<String, _>(&*bytes)
+            .unwrap()
+            .is_empty());
+    }
+}
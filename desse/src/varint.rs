@@ -0,0 +1,347 @@
+#![cfg(feature = "dynamic")]
+use alloc::vec::Vec;
+
+use crate::{
+    DesseBorrowed, DesseDynamic, DesseOwned, Error, ErrorKind, LimitedReader, Reader, Result,
+    Writer,
+};
+
+/// Wrapper for integers which are serialized using a variable length encoding
+///
+/// Unsigned integers are encoded using LEB128, i.e., seven bits of value (starting from least significant bits) per
+/// byte with most significant bit of each byte set if more bytes follow. Signed integers are first mapped to unsigned
+/// integers using ZigZag encoding (`0, -1, 1, -2, ...` to `0, 1, 2, 3, ...`), so that integers with small magnitude
+/// take fewer bytes.
+///
+/// Encoding of a value is at most `Varint::<T>::MAX_SIZE` bytes long. Deserialization rejects (with
+/// `ErrorKind::InvalidVarint`) encodings with more bytes than necessary and encodings of values which do not fit in
+/// `T`, so that each value has exactly one valid encoding.
+///
+/// # Example
+///
+/// ```
+/// use desse::{DesseDynamic, Varint};
+///
+/// let ids = vec![Varint(1u64), Varint(300)];
+/// let bytes = ids.serialize().unwrap();
+///
+/// // Length prefix of `Vec` followed by `1` and `300`
+/// assert_eq!(8 + 1 + 2, bytes.len());
+/// assert_eq!(ids, Vec::<Varint<u64>>::deserialize_from(&*bytes).unwrap());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Varint<T>(pub T);
+
+/// Returns error for an invalid varint encoding with offending byte at `offset`
+#[cold]
+fn invalid_varint<T>(offset: usize) -> Error {
+    Error::from(ErrorKind::InvalidVarint)
+        .with_type_name(core::any::type_name::<Varint<T>>())
+        .with_offset(offset)
+}
+
+macro_rules! impl_desse_dynamic_varint {
+    ($type: ty, $max_size: expr) => {
+        impl Varint<$type> {
+            /// Maximum number of bytes in encoding of a value
+            pub const MAX_SIZE: usize = $max_size;
+
+            /// Encodes value into `bytes` and returns number of bytes used
+            #[inline]
+            fn encode(&self, bytes: &mut [u8; $max_size]) -> usize {
+                let mut value = self.0;
+                let mut len = 0;
+
+                while value >= 0x80 {
+                    bytes[len] = (value as u8) | 0x80;
+                    value >>= 7;
+                    len += 1;
+                }
+
+                bytes[len] = value as u8;
+                len + 1
+            }
+
+            /// Decodes a value from `reader`
+            #[inline]
+            fn decode<R: Reader>(mut reader: R) -> Result<Self> {
+                let mut value: $type = 0;
+
+                for i in 0..Self::MAX_SIZE {
+                    let byte = reader.read(1)?[0];
+
+                    // Bits of last byte beyond the width of integer (including continuation bit) must be zero
+                    if i == Self::MAX_SIZE - 1 && byte >> (<$type>::BITS as usize - 7 * i) != 0 {
+                        return Err(invalid_varint::<$type>(i));
+                    }
+
+                    value |= <$type>::from(byte & 0x7F) << (7 * i);
+
+                    if byte & 0x80 == 0 {
+                        // A zero byte after first byte only adds a redundant group of zero bits
+                        if i > 0 && byte == 0 {
+                            return Err(invalid_varint::<$type>(i));
+                        }
+
+                        return Ok(Varint(value));
+                    }
+                }
+
+                unreachable!("Last byte of varint should not have continuation bit")
+            }
+        }
+
+        impl DesseDynamic for Varint<$type> {
+            type Output = Self;
+
+            const MIN_SERIALIZED_SIZE: usize = 1;
+
+            #[inline]
+            fn serialized_size(&self) -> usize {
+                let bits = <$type>::BITS - self.0.leading_zeros();
+
+                if bits == 0 {
+                    1
+                } else {
+                    bits.div_ceil(7) as usize
+                }
+            }
+
+            #[inline]
+            fn serialize(&self) -> Result<Vec<u8>> {
+                let mut bytes = [0; $max_size];
+                let len = self.encode(&mut bytes);
+                Ok(bytes[..len].to_vec())
+            }
+
+            #[inline]
+            fn serialize_into<W: Writer>(&self, mut writer: W) -> Result<()> {
+                let mut bytes = [0; $max_size];
+                let len = self.encode(&mut bytes);
+                writer.write(&bytes[..len])
+            }
+
+            #[inline]
+            fn serialize_into_unchecked<W: Writer>(&self, mut writer: W) -> Result<()> {
+                let mut bytes = [0; $max_size];
+                let len = self.encode(&mut bytes);
+                writer.write_unchecked(&bytes[..len])
+            }
+        }
+
+        impl DesseOwned for Varint<$type> {
+            #[inline]
+            fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+                Self::decode(reader)
+            }
+
+            /// Length of a varint is not known before reading it, so, this function reads input in same way as
+            /// `deserialize_from()`
+            #[inline]
+            fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+                Self::decode(reader)
+            }
+        }
+
+        impl<'de> DesseBorrowed<'de> for Varint<$type> {
+            #[inline]
+            fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+                Self::decode(reader)
+            }
+        }
+    };
+}
+
+/// Signed integers are ZigZag encoded and serialized as `Varint` of unsigned integer of same width
+macro_rules! impl_desse_dynamic_varint_signed {
+    ($type: ty, $unsigned: ty) => {
+        impl Varint<$type> {
+            /// Maximum number of bytes in encoding of a value
+            pub const MAX_SIZE: usize = Varint::<$unsigned>::MAX_SIZE;
+
+            /// Maps value to an unsigned integer using ZigZag encoding
+            #[inline]
+            fn zigzag(&self) -> Varint<$unsigned> {
+                Varint(((self.0 << 1) ^ (self.0 >> (<$type>::BITS - 1))) as $unsigned)
+            }
+
+            /// Maps an unsigned integer back to value using ZigZag decoding
+            #[inline]
+            fn unzigzag(value: Varint<$unsigned>) -> Self {
+                Varint(((value.0 >> 1) as $type) ^ -((value.0 & 1) as $type))
+            }
+        }
+
+        impl DesseDynamic for Varint<$type> {
+            type Output = Self;
+
+            const MIN_SERIALIZED_SIZE: usize = 1;
+
+            #[inline]
+            fn serialized_size(&self) -> usize {
+                self.zigzag().serialized_size()
+            }
+
+            #[inline]
+            fn serialize(&self) -> Result<Vec<u8>> {
+                DesseDynamic::serialize(&self.zigzag())
+            }
+
+            #[inline]
+            fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+                DesseDynamic::serialize_into(&self.zigzag(), writer)
+            }
+
+            #[inline]
+            fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+                DesseDynamic::serialize_into_unchecked(&self.zigzag(), writer)
+            }
+        }
+
+        impl DesseOwned for Varint<$type> {
+            #[inline]
+            fn deserialize_owned<R: Reader>(reader: R) -> Result<Self::Output> {
+                Varint::<$unsigned>::decode(reader)
+                    .map(Self::unzigzag)
+                    .map_err(|err| err.with_type_name(core::any::type_name::<Self>()))
+            }
+
+            #[inline]
+            fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self::Output> {
+                Self::deserialize_owned(reader)
+            }
+        }
+
+        impl<'de> DesseBorrowed<'de> for Varint<$type> {
+            #[inline]
+            fn deserialize_borrowed_from(reader: &mut LimitedReader<&'de [u8]>) -> Result<Self> {
+                <Self as DesseOwned>::deserialize_owned(reader)
+            }
+        }
+    };
+}
+
+impl_desse_dynamic_varint!(u8, 2);
+impl_desse_dynamic_varint!(u16, 3);
+impl_desse_dynamic_varint!(u32, 5);
+impl_desse_dynamic_varint!(u64, 10);
+impl_desse_dynamic_varint!(u128, 19);
+
+impl_desse_dynamic_varint_signed!(i8, u8);
+impl_desse_dynamic_varint_signed!(i16, u16);
+impl_desse_dynamic_varint_signed!(i32, u32);
+impl_desse_dynamic_varint_signed!(i64, u64);
+impl_desse_dynamic_varint_signed!(i128, u128);
+
+#[cfg(test)]
+mod tests {
+    // For initializing global memory allocator
+    extern crate std;
+
+    use super::*;
+
+    use alloc::vec;
+
+    macro_rules! impl_desse_varint_test {
+        ($type: ty, $name: ident) => {
+            #[test]
+            fn $name() {
+                for value in [
+                    <$type>::MIN,
+                    <$type>::MAX,
+                    0,
+                    1,
+                    0x7F,
+                    rand::random::<$type>(),
+                ] {
+                    let varint = Varint(value);
+                    let bytes = DesseDynamic::serialize(&varint).unwrap();
+
+                    assert_eq!(varint.serialized_size(), bytes.len());
+                    assert!(bytes.len() <= Varint::<$type>::MAX_SIZE);
+                    assert_eq!(
+                        varint,
+                        Varint::<$type>::deserialize_exact(&bytes).unwrap(),
+                        "Invalid serialization / deserialization"
+                    );
+                    assert_eq!(
+                        varint,
+                        Varint::<$type>::deserialize_borrowed(&bytes).unwrap()
+                    );
+                }
+            }
+        };
+    }
+
+    impl_desse_varint_test!(u8, check_varint_u8);
+    impl_desse_varint_test!(u16, check_varint_u16);
+    impl_desse_varint_test!(u32, check_varint_u32);
+    impl_desse_varint_test!(u64, check_varint_u64);
+    impl_desse_varint_test!(u128, check_varint_u128);
+
+    impl_desse_varint_test!(i8, check_varint_i8);
+    impl_desse_varint_test!(i16, check_varint_i16);
+    impl_desse_varint_test!(i32, check_varint_i32);
+    impl_desse_varint_test!(i64, check_varint_i64);
+    impl_desse_varint_test!(i128, check_varint_i128);
+
+    #[test]
+    fn check_varint_encoding() {
+        assert_eq!(vec![0], DesseDynamic::serialize(&Varint(0u32)).unwrap());
+        assert_eq!(
+            vec![0x7F],
+            DesseDynamic::serialize(&Varint(127u32)).unwrap()
+        );
+        assert_eq!(
+            vec![0xAC, 0x02],
+            DesseDynamic::serialize(&Varint(300u32)).unwrap()
+        );
+        assert_eq!(
+            vec![0xFF, 0x01],
+            DesseDynamic::serialize(&Varint(u8::MAX)).unwrap()
+        );
+
+        // ZigZag encoding
+        assert_eq!(vec![1], DesseDynamic::serialize(&Varint(-1i64)).unwrap());
+        assert_eq!(vec![2], DesseDynamic::serialize(&Varint(1i64)).unwrap());
+        assert_eq!(vec![3], DesseDynamic::serialize(&Varint(-2i64)).unwrap());
+        assert_eq!(
+            vec![0xFF, 0x01],
+            DesseDynamic::serialize(&Varint(i8::MIN)).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_varint_overlong() {
+        let err = Varint::<u32>::deserialize_from(&[0x80, 0x00][..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidVarint, err.kind());
+        assert_eq!(Some(1), err.offset());
+
+        let err = Varint::<i32>::deserialize_from(&[0x81, 0x80, 0x00][..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidVarint, err.kind());
+        assert_eq!(Some(2), err.offset());
+    }
+
+    #[test]
+    fn check_varint_overflow() {
+        // `256` does not fit in `u8`
+        let err = Varint::<u8>::deserialize_from(&[0x80, 0x02][..]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidVarint, err.kind());
+        assert_eq!(Some(1), err.offset());
+
+        // Continuation bit in last possible byte
+        let mut bytes = vec![0xFF; Varint::<u64>::MAX_SIZE];
+        let err = Varint::<u64>::deserialize_from(&*bytes).unwrap_err();
+        assert_eq!(ErrorKind::InvalidVarint, err.kind());
+        assert_eq!(Some(9), err.offset());
+
+        bytes[9] = 0x01;
+        assert_eq!(
+            Varint(u64::MAX),
+            Varint::<u64>::deserialize_from(&*bytes).unwrap()
+        );
+
+        let err = Varint::<u64>::deserialize_from(&bytes[..5]).unwrap_err();
+        assert_eq!(ErrorKind::InvalidSliceLength, err.kind());
+    }
+}
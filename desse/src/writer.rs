@@ -6,6 +6,21 @@ use crate::{ErrorKind, Result};
 
 /// Trait for doing `write` operations.
 pub trait Writer: Sealed {
+    /// Writer to which current writer forwards its operations (`Self` for writers which are not references)
+    #[doc(hidden)]
+    type Inner: Writer + ?Sized
+    where
+        Self: Sized;
+
+    /// Returns writer to which current writer forwards its operations
+    ///
+    /// Nested values are serialized into this writer instead of `&mut self` so that serializing a recursive type does
+    /// not instantiate an unbounded chain of `&mut &mut ...` writers.
+    #[doc(hidden)]
+    fn inner_mut(&mut self) -> &mut Self::Inner
+    where
+        Self: Sized;
+
     /// Writes the `buf` in current object.
     fn write(&mut self, buf: &[u8]) -> Result<()>;
 
@@ -18,6 +33,13 @@ pub trait Writer: Sealed {
 }
 
 impl Writer for &mut [u8] {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
     #[inline]
     fn write(&mut self, buf: &[u8]) -> Result<()> {
         if self.len() < buf.len() {
@@ -29,7 +51,7 @@ impl Writer for &mut [u8] {
 
     #[inline]
     fn write_unchecked(&mut self, buf: &[u8]) -> Result<()> {
-        let (a, b) = core::mem::replace(self, &mut []).split_at_mut(buf.len());
+        let (a, b) = core::mem::take(self).split_at_mut(buf.len());
         a.copy_from_slice(buf);
         *self = b;
         Ok(())
@@ -37,6 +59,13 @@ impl Writer for &mut [u8] {
 }
 
 impl Writer for Vec<u8> {
+    type Inner = Self;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
     #[inline]
     fn write(&mut self, buf: &[u8]) -> Result<()> {
         self.write_unchecked(buf)
@@ -51,8 +80,15 @@ impl Writer for Vec<u8> {
 
 impl<W> Writer for &mut W
 where
-    W: Writer,
+    W: Writer + ?Sized,
 {
+    type Inner = W;
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut Self::Inner {
+        self
+    }
+
     #[inline]
     fn write(&mut self, buf: &[u8]) -> Result<()> {
         (**self).write(buf)
@@ -0,0 +1,123 @@
+use std::env;
+use std::fs;
+use std::process::Command;
+
+use desse::{crc32c, CHeader, DesseCType, DesseSized, DesseStatic};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic, DesseCType)]
+#[desse(reserved = 4)]
+struct Reading {
+    sensor: u8,
+    value: i32,
+    timestamp: u64,
+    raw: [u16; 3],
+    state: SensorState,
+    r#type: u8,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic, DesseCType)]
+#[desse(version = 1, checksum)]
+enum SensorState {
+    Idle,
+    #[desse(reserved = 1)]
+    Fault(u16, bool),
+    Active {
+        gain: i16,
+        limit: u32,
+    },
+}
+
+fn bytes_literal(bytes: &[u8]) -> String {
+    let bytes = bytes
+        .iter()
+        .map(|byte| byte.to_string())
+        .collect::<Vec<_>>();
+
+    format!("{{ {} }}", bytes.join(", "))
+}
+
+#[test]
+fn check_c_header_compiles() {
+    let reading = Reading {
+        sensor: 7,
+        value: -123_456,
+        timestamp: 0x0102_0304_0506_0708,
+        raw: [1, 2, 3],
+        state: SensorState::Active {
+            gain: -42,
+            limit: 100_000,
+        },
+        r#type: 3,
+    };
+    let fault = SensorState::Fault(0xBEEF, true);
+
+    let mut header = CHeader::new("READING_H");
+    header.add::<Reading>();
+
+    let source = format!(
+        r#"
+#include <string.h>
+#include "reading.h"
+
+_Static_assert(READING_SIZE == {reading_size}, "Invalid reading size");
+_Static_assert(SENSOR_STATE_SIZE == {state_size}, "Invalid state size");
+
+int main(void) {{
+    const uint8_t reading_bytes[] = {reading_bytes};
+    const uint8_t fault_bytes[] = {fault_bytes};
+    Reading reading;
+    SensorState fault;
+
+    memcpy(&reading, reading_bytes, sizeof(reading));
+    memcpy(&fault, fault_bytes, sizeof(fault));
+
+    if (Reading_get_sensor(&reading) != 7) return 1;
+    if (Reading_get_value(&reading) != -123456) return 2;
+    if (Reading_get_timestamp(&reading) != 0x0102030405060708ULL) return 3;
+    if (SensorState_get_tag(&reading.state) != SENSOR_STATE_ACTIVE) return 4;
+    if (SensorState_Active_get_gain(&reading.state) != -42) return 5;
+    if (SensorState_Active_get_limit(&reading.state) != 100000) return 6;
+    if (SensorState_get_version(&fault) != 1) return 7;
+    if (SensorState_get_tag(&fault) != SENSOR_STATE_FAULT) return 8;
+    if (SensorState_Fault_get__0(&fault) != 0xBEEF) return 9;
+    if (SensorState_Fault_get__1(&fault) != 1) return 10;
+    if (SensorState_get_checksum(&fault) != {fault_checksum}u) return 11;
+
+    Reading_set_value(&reading, 99);
+    if (memcmp(&reading, reading_bytes, offsetof(Reading, value)) != 0) return 12;
+    if (Reading_get_value(&reading) != 99) return 13;
+    if (Reading_get_type(&reading) != 3) return 14;
+
+    return 0;
+}}
+"#,
+        reading_size = Reading::SIZE,
+        state_size = SensorState::SIZE,
+        reading_bytes = bytes_literal(&reading.serialize()),
+        fault_bytes = bytes_literal(&fault.serialize()),
+        fault_checksum = crc32c(&fault.serialize()[..(SensorState::SIZE - 4)]),
+    );
+
+    let dir = env::temp_dir().join(format!("desse-c-header-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(dir.join("reading.h"), header.to_string()).unwrap();
+    fs::write(dir.join("main.c"), source).unwrap();
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let binary = dir.join("reading");
+
+    let status = Command::new(compiler)
+        .args(["-std=c11", "-Wall", "-Werror", "-o"])
+        .arg(&binary)
+        .arg(dir.join("main.c"))
+        .status()
+        .expect("Unable to run C compiler");
+    assert!(status.success(), "Unable to compile generated header");
+
+    // Each failed check exits with its own code
+    let status = Command::new(&binary).status().unwrap();
+    assert_eq!(Some(0), status.code(), "Invalid values read from C");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,81 @@
+use desse::{crc32c, DesseSized, DesseStatic, DesseVersioned, ErrorKind};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(checksum)]
+struct Block {
+    id: u32,
+    data: [u8; 8],
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(version = 2, checksum, reserved = 2)]
+struct Entry {
+    key: u16,
+    value: i64,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(checksum)]
+enum Command {
+    Stop,
+    Move { x: i16, y: i16 },
+}
+
+#[test]
+fn check_checksum_size() {
+    assert_eq!(4 + 8 + 4, Block::SIZE);
+    assert_eq!(1 + 2 + 8 + 2 + 4, Entry::SIZE);
+    assert_eq!(1 + 4 + 4, Command::SIZE);
+}
+
+#[test]
+fn check_checksum_trailer() {
+    let block = Block {
+        id: 9,
+        data: [1, 2, 3, 4, 5, 6, 7, 8],
+    };
+    let serialized = block.serialize();
+
+    assert_eq!(crc32c(&serialized[..12]).to_le_bytes(), serialized[12..]);
+    assert_eq!(block, Block::deserialize_from(&serialized).unwrap());
+    assert_eq!(block, Block::deserialize_from_strict(&serialized).unwrap());
+
+    let entry = Entry { key: 1, value: -1 };
+    let serialized = entry.serialize();
+
+    assert_eq!(2, serialized[0]);
+    assert_eq!(crc32c(&serialized[..13]).to_le_bytes(), serialized[13..]);
+    assert_eq!(entry, Entry::deserialize_versioned(&serialized).unwrap());
+
+    let command = Command::Move { x: -1, y: 2 };
+    assert_eq!(
+        command,
+        Command::deserialize_from(&command.serialize()).unwrap()
+    );
+}
+
+#[test]
+fn check_checksum_mismatch() {
+    let block = Block {
+        id: 9,
+        data: [0; 8],
+    };
+
+    for i in 0..Block::SIZE {
+        let mut serialized = block.serialize();
+        serialized[i] ^= 0x10;
+
+        assert_eq!(
+            ErrorKind::ChecksumMismatch,
+            Block::deserialize_from(&serialized).unwrap_err().kind()
+        );
+    }
+
+    let mut serialized = Command::Stop.serialize();
+    serialized[2] = 1;
+
+    assert_eq!(
+        ErrorKind::ChecksumMismatch,
+        Command::deserialize_from(&serialized).unwrap_err().kind()
+    );
+}
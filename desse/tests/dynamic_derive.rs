@@ -0,0 +1,351 @@
+use std::borrow::Cow;
+
+use desse::{DesseBorrowed, DesseDynamic, DesseOwned, ErrorKind, Reader, Result, Writer};
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+struct Record {
+    id: u32,
+    name: String,
+    tags: Vec<String>,
+    parent: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+struct Pair(u8, String);
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+struct Empty;
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+#[desse(tag_type = "u16")]
+enum Event {
+    Start,
+    #[desse(tag = 7)]
+    Rename(u32, String),
+    Update {
+        record: Record,
+        r#type: char,
+    },
+}
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+struct Entry<'a> {
+    key: &'a str,
+    data: &'a [u8],
+    label: Cow<'a, str>,
+    aliases: Vec<&'a str>,
+    note: Option<&'a str>,
+}
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+enum Token<'a> {
+    Word(&'a str),
+    Number(i64),
+}
+
+/// Type implementing only `DesseDynamic` (and `DesseOwned`) by hand
+#[derive(Debug, PartialEq)]
+struct Custom(u16);
+
+impl DesseDynamic for Custom {
+    type Output = Self;
+
+    const MIN_SERIALIZED_SIZE: usize = 2;
+
+    fn serialized_size(&self) -> usize {
+        self.0.serialized_size()
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>> {
+        self.0.serialize()
+    }
+
+    fn serialize_into<W: Writer>(&self, writer: W) -> Result<()> {
+        self.0.serialize_into(writer)
+    }
+
+    fn serialize_into_unchecked<W: Writer>(&self, writer: W) -> Result<()> {
+        self.0.serialize_into_unchecked(writer)
+    }
+}
+
+impl DesseOwned for Custom {
+    fn deserialize_owned<R: Reader>(reader: R) -> Result<Self> {
+        u16::deserialize_from(reader).map(Custom)
+    }
+
+    fn deserialize_owned_unchecked<R: Reader>(reader: R) -> Result<Self> {
+        u16::deserialize_from_unchecked(reader).map(Custom)
+    }
+}
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+struct Labeled<'a> {
+    label: &'a str,
+    custom: Custom,
+}
+
+fn record() -> Record {
+    Record {
+        id: 42,
+        name: "record".to_string(),
+        tags: vec!["a".to_string(), "bc".to_string()],
+        parent: Some(7),
+    }
+}
+
+fn entry() -> Entry<'static> {
+    Entry {
+        key: "key",
+        data: &[1, 2, 3],
+        label: Cow::Borrowed("label"),
+        aliases: vec!["x", "yz"],
+        note: None,
+    }
+}
+
+#[test]
+fn check_struct() {
+    let record = record();
+    let bytes = record.serialize().unwrap();
+
+    assert_eq!(record.serialized_size(), bytes.len());
+    assert_eq!(4 + 8 + 6 + 8 + 9 + 10 + 9, bytes.len());
+    assert_eq!(4 + 8 + 8 + 1, Record::MIN_SERIALIZED_SIZE);
+
+    assert_eq!(record, Record::deserialize_from(&*bytes).unwrap());
+    assert_eq!(record, Record::deserialize_borrowed(&bytes).unwrap());
+
+    let pair = Pair(1, "one".to_string());
+    assert_eq!(
+        pair,
+        Pair::deserialize_exact(&pair.serialize().unwrap()).unwrap()
+    );
+
+    assert!(Empty.serialize().unwrap().is_empty());
+    assert_eq!(Empty, Empty::deserialize_exact(&[]).unwrap());
+}
+
+#[test]
+fn check_enum() {
+    for event in [
+        Event::Start,
+        Event::Rename(3, "new".to_string()),
+        Event::Update {
+            record: record(),
+            r#type: 'u',
+        },
+    ] {
+        let bytes = event.serialize().unwrap();
+
+        assert_eq!(event.serialized_size(), bytes.len());
+        assert_eq!(event, Event::deserialize_exact(&bytes).unwrap());
+        assert_eq!(event, Event::deserialize_borrowed(&bytes).unwrap());
+    }
+
+    assert_eq!(
+        &[7, 0],
+        &Event::Rename(3, "new".to_string()).serialize().unwrap()[..2]
+    );
+
+    let err = Event::deserialize_from(&[2u8, 0][..]).unwrap_err();
+    assert_eq!(ErrorKind::InvalidTag, err.kind());
+    assert_eq!(Some(0), err.offset());
+}
+
+#[test]
+fn check_borrowed_struct() {
+    let entry = entry();
+    let bytes = entry.serialize().unwrap();
+
+    let new_entry = Entry::deserialize_borrowed(&bytes).unwrap();
+    assert_eq!(entry, new_entry);
+
+    // Strings and byte slices point into serialized bytes
+    let range = bytes.as_ptr_range();
+    assert!(range.contains(&new_entry.key.as_ptr()));
+    assert!(range.contains(&new_entry.data.as_ptr()));
+    assert!(range.contains(&new_entry.aliases[1].as_ptr()));
+
+    match new_entry.label {
+        Cow::Borrowed(label) => assert!(range.contains(&label.as_ptr())),
+        Cow::Owned(_) => panic!("Expected borrowed label"),
+    }
+
+    let tokens = vec![Token::Word("hello"), Token::Number(-5)];
+    let bytes = tokens.serialize().unwrap();
+    assert_eq!(tokens, Vec::<Token>::deserialize_borrowed(&bytes).unwrap());
+}
+
+#[test]
+fn check_owned_field() {
+    let labeled = Labeled {
+        label: "label",
+        custom: Custom(7),
+    };
+    let bytes = labeled.serialize().unwrap();
+
+    assert_eq!(8 + 5 + 2, bytes.len());
+    assert_eq!(labeled, Labeled::deserialize_borrowed(&bytes).unwrap());
+}
+
+#[test]
+fn check_error_context() {
+    let mut bytes = record().serialize().unwrap();
+    bytes[4 + 8 + 2] = 0xFF;
+
+    let err = Record::deserialize_from(&*bytes).unwrap_err();
+    assert_eq!(ErrorKind::InvalidStr, err.kind());
+    assert_eq!(Some(4 + 8 + 2), err.offset());
+    assert_eq!("Record.name", err.path().to_string());
+
+    let err = Record::deserialize_borrowed(&bytes).unwrap_err();
+    assert_eq!(Some(4 + 8 + 2), err.offset());
+    assert_eq!("Record.name", err.path().to_string());
+
+    let mut bytes = Token::Word("hello").serialize().unwrap();
+    bytes[1 + 8] = 0xFF;
+
+    let err = Token::deserialize_borrowed(&bytes).unwrap_err();
+    assert_eq!(Some(1 + 8), err.offset());
+    assert_eq!("Token::Word.0", err.path().to_string());
+}
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+enum Node {
+    Leaf(u32),
+    Branch(Vec<Node>),
+    Boxed(Box<Node>),
+}
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+struct List {
+    value: u8,
+    next: Option<Box<List>>,
+}
+
+#[derive(Debug, PartialEq, DesseDynamic)]
+struct Chain {
+    value: u8,
+    next: core::result::Result<u8, Box<Chain>>,
+}
+
+#[test]
+fn check_recursive() {
+    let node = Node::Branch(vec![
+        Node::Leaf(1),
+        Node::Boxed(Box::new(Node::Branch(vec![Node::Leaf(2)]))),
+    ]);
+    let bytes = node.serialize().unwrap();
+
+    assert_eq!(node.serialized_size(), bytes.len());
+    assert_eq!(node, Node::deserialize_exact(&bytes).unwrap());
+    assert_eq!(node, Node::deserialize_borrowed(&bytes).unwrap());
+
+    let list = List {
+        value: 1,
+        next: Some(Box::new(List {
+            value: 2,
+            next: None,
+        })),
+    };
+    let bytes = list.serialize().unwrap();
+
+    assert_eq!(2 + 2, bytes.len());
+    assert_eq!(list, List::deserialize_exact(&bytes).unwrap());
+
+    let chain = Chain {
+        value: 1,
+        next: Err(Box::new(Chain {
+            value: 2,
+            next: Ok(3),
+        })),
+    };
+    let bytes = chain.serialize().unwrap();
+
+    assert_eq!(1 + 1, Chain::MIN_SERIALIZED_SIZE);
+    assert_eq!(2 + 3, bytes.len());
+    assert_eq!(chain, Chain::deserialize_exact(&bytes).unwrap());
+    assert_eq!(chain, Chain::deserialize_borrowed(&bytes).unwrap());
+}
+
+#[test]
+fn check_recursion_limit() {
+    // Deeply nested `Branch` with one child at each level
+    let mut bytes = Vec::new();
+
+    for _ in 0..100_000 {
+        bytes.push(1);
+        bytes.extend_from_slice(&1u64.to_le_bytes());
+    }
+
+    // Deeply nested `Boxed`
+    let boxed = vec![2u8; 100_000];
+
+    let limits = desse::Limits::new();
+
+    let err = Node::deserialize_from_with_limits(&*bytes, &limits).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+    let err = Node::deserialize_borrowed_with_limits(&bytes, &limits).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+    let err = Node::deserialize_from_with_limits(&*boxed, &limits).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+    let err = Node::deserialize_borrowed_with_limits(&boxed, &limits).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded, err.kind());
+}
+
+#[test]
+fn check_default_recursion_limit() {
+    // Deeply nested `Boxed` which overflows the stack without a limit on nesting depth
+    let mut bytes = vec![2u8; 1024 * 1024];
+    bytes.push(0);
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+
+    let err = Node::deserialize_from(&*bytes).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+    let err = Node::deserialize_from_unchecked(&*bytes).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+    let err = Node::deserialize_exact(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+    let err = Node::deserialize_borrowed(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded, err.kind());
+
+    // Nesting up to default maximum depth is allowed
+    let depth = desse::Limits::DEFAULT_MAX_DEPTH;
+    let bytes = &bytes[bytes.len() - depth - 5..];
+
+    assert!(Node::deserialize_exact(bytes).is_ok());
+    assert!(Node::deserialize_borrowed(bytes).is_ok());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn check_sorted_field() {
+    use std::collections::HashMap;
+
+    use desse::Sorted;
+
+    #[derive(Debug, PartialEq, DesseDynamic)]
+    struct Index {
+        name: String,
+        entries: Sorted<HashMap<String, u32>>,
+    }
+
+    let index = |order: &[u32]| Index {
+        name: "index".to_string(),
+        entries: Sorted(order.iter().map(|i| (i.to_string(), *i)).collect()),
+    };
+
+    let a = index(&(0..32).collect::<Vec<_>>());
+    let b = index(&(0..32).rev().collect::<Vec<_>>());
+    let bytes = a.serialize().unwrap();
+
+    assert_eq!(bytes, b.serialize().unwrap());
+    assert_eq!(a, Index::deserialize_exact(&bytes).unwrap());
+}
@@ -0,0 +1,134 @@
+use desse::{DesseDynamic, DesseSized, DesseStatic, ErrorKind, PathSegment};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+struct Line {
+    quantity: u16,
+    sku: char,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+struct Order {
+    id: u32,
+    tags: [char; 2],
+    line: Line,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+enum Shape {
+    #[desse(reserved = 2)]
+    Circle {
+        radius: u32,
+    },
+    Label(u8, char),
+}
+
+fn order() -> Order {
+    Order {
+        id: 7,
+        tags: ['a', 'b'],
+        line: Line {
+            quantity: 3,
+            sku: 'x',
+        },
+    }
+}
+
+#[test]
+fn check_nested_field_context() {
+    let mut bytes = order().serialize();
+    bytes[14..18].copy_from_slice(&0xD800u32.to_le_bytes());
+
+    let err = Order::deserialize_from(&bytes).unwrap_err();
+
+    assert_eq!(ErrorKind::InvalidChar, err.kind());
+    assert_eq!(Some(14), err.offset());
+    assert_eq!(Some("char"), err.type_name());
+    assert_eq!("Order.line.sku", err.path().to_string());
+    assert_eq!(Some("Order"), err.path().root());
+    assert_eq!(
+        vec![PathSegment::Field("line"), PathSegment::Field("sku")],
+        err.path().segments().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "Deserialization from bytes to char failed at offset 14 in `Order.line.sku` while decoding `char`",
+        err.to_string()
+    );
+}
+
+#[test]
+fn check_array_element_context() {
+    let mut bytes = order().serialize();
+    bytes[8..12].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    let err = Order::deserialize_from(&bytes).unwrap_err();
+
+    assert_eq!(Some(8), err.offset());
+    assert_eq!("Order.tags[1]", err.path().to_string());
+}
+
+#[test]
+fn check_enum_context() {
+    let mut bytes = Shape::Label(1, 'a').serialize();
+    bytes[2..6].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    let err = Shape::deserialize_from(&bytes).unwrap_err();
+
+    assert_eq!(Some(2), err.offset());
+    assert_eq!("Shape::Label.1", err.path().to_string());
+
+    let mut bytes = Shape::Circle { radius: 1 }.serialize();
+    bytes[0] = 9;
+
+    let err = Shape::deserialize_from(&bytes).unwrap_err();
+
+    assert_eq!(ErrorKind::InvalidTag, err.kind());
+    assert_eq!(Some(0), err.offset());
+    assert!(err.type_name().unwrap().ends_with("Shape"));
+    assert!(err.path().is_empty());
+
+    let mut bytes = Shape::Circle { radius: 1 }.serialize();
+    bytes[5] = 1;
+
+    let err = Shape::deserialize_from_strict(&bytes).unwrap_err();
+
+    assert_eq!(ErrorKind::NonZeroReserved, err.kind());
+    assert_eq!(Some(5), err.offset());
+}
+
+#[test]
+fn check_sequence_context() {
+    let values = vec!["hello", "wörld", "!"];
+    let mut bytes = DesseDynamic::serialize(&values).unwrap();
+
+    // Second byte of `ö` in second string
+    let position = 8 + 13 + 8 + 2;
+    bytes[position] = 0xFF;
+
+    let err = Vec::<String>::deserialize_from(&*bytes).unwrap_err();
+
+    // Invalid sequence starts at first byte of `ö`
+    assert_eq!(ErrorKind::InvalidStr, err.kind());
+    assert_eq!(Some(position - 1), err.offset());
+    assert_eq!("[1]", err.path().to_string());
+    assert_eq!(Some(core::any::type_name::<String>()), err.type_name());
+
+    let mut bytes = DesseDynamic::serialize(&vec![None, Some('a'), Some('b')]).unwrap();
+    bytes[8 + 1 + 5 + 1..].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    let err = Vec::<Option<char>>::deserialize_from(&*bytes).unwrap_err();
+
+    assert_eq!(ErrorKind::InvalidChar, err.kind());
+    assert_eq!(Some(8 + 1 + 5 + 1), err.offset());
+    assert_eq!("[2]", err.path().to_string());
+    assert_eq!(Some("char"), err.type_name());
+}
+
+#[test]
+fn check_error_without_context() {
+    let err = <u32 as DesseDynamic>::deserialize_from(&[][..]).unwrap_err();
+
+    assert_eq!(None, err.offset());
+    assert_eq!(None, err.type_name());
+    assert!(err.path().is_empty());
+    assert_eq!("Input slice is of invalid length", err.to_string());
+}
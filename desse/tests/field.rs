@@ -0,0 +1,174 @@
+use desse::{DesseSized, DesseStatic, RecordSlice, RecordSliceMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, DesseSized, DesseStatic)]
+#[desse(version = 1, checksum, field_consts)]
+struct Entry {
+    flags: [u8; 3],
+    score: i32,
+    r#type: u64,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(field_consts)]
+struct Pair(u16, char);
+
+mod record {
+    use desse::{DesseSized, DesseStatic};
+
+    #[derive(DesseSized, DesseStatic)]
+    #[desse(field_consts)]
+    pub struct Record {
+        pub id: u32,
+        secret: u16,
+    }
+
+    /// Field constants are not generated without `field_consts`, so, these do not conflict
+    #[derive(DesseSized, DesseStatic)]
+    pub struct Plain {
+        pub id: u32,
+    }
+
+    impl Plain {
+        pub const FIELD_ID: usize = 0;
+    }
+
+    impl Record {
+        pub fn secret_offset() -> usize {
+            Self::FIELD_SECRET.offset()
+        }
+    }
+}
+
+fn entries() -> Vec<Entry> {
+    // Simple LCG, so that test does not depend on an RNG
+    let mut state = 0x2545_F491u32;
+
+    (0..200)
+        .map(|i| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+
+            Entry {
+                flags: [(state >> 24) as u8, (state >> 16) as u8, i as u8],
+                score: (state as i32) >> 8,
+                r#type: u64::from(state) << 20,
+            }
+        })
+        .collect()
+}
+
+fn serialize_all(entries: &[Entry]) -> Vec<u8> {
+    entries.iter().flat_map(|entry| entry.serialize()).collect()
+}
+
+#[test]
+fn check_field_offsets() {
+    assert_eq!(1, Entry::FIELD_FLAGS.offset());
+    assert_eq!(4, Entry::FIELD_SCORE.offset());
+    assert_eq!(8, Entry::FIELD_TYPE.offset());
+
+    assert_eq!(0, Pair::FIELD_0.offset());
+    assert_eq!(2, Pair::FIELD_1.offset());
+
+    let entry = entries()[0];
+    let bytes = entry.serialize();
+
+    assert_eq!(&entry.score.serialize(), Entry::FIELD_SCORE.bytes(&bytes));
+    assert_eq!(&entry.r#type.serialize(), Entry::FIELD_TYPE.bytes(&bytes));
+}
+
+#[test]
+fn check_field_const_visibility() {
+    assert_eq!(0, record::Record::FIELD_ID.offset());
+    assert_eq!(4, record::Record::secret_offset());
+    assert_eq!(0, record::Plain::FIELD_ID);
+}
+
+#[test]
+fn check_sort_and_binary_search_by_field() {
+    let mut expected = entries();
+    let mut bytes = serialize_all(&expected);
+
+    let mut records = RecordSliceMut::<Entry>::new(&mut bytes).unwrap();
+    records.sort_by_field(Entry::FIELD_SCORE);
+
+    expected.sort_by_key(|entry| entry.score);
+
+    let records = RecordSlice::<Entry>::new(&bytes).unwrap();
+    let scores = records
+        .iter()
+        .map(|entry| entry.unwrap().score)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        expected.iter().map(|entry| entry.score).collect::<Vec<_>>(),
+        scores
+    );
+
+    for entry in &expected {
+        let index = records
+            .binary_search_by_field(Entry::FIELD_SCORE, &entry.score)
+            .unwrap();
+        assert_eq!(entry.score, records.get(index).unwrap().score);
+
+        for score in &[entry.score - 1, entry.score + 1] {
+            let result = records.binary_search_by_field(Entry::FIELD_SCORE, score);
+
+            match scores.binary_search(score) {
+                Ok(_) => assert_eq!(*score, records.get(result.unwrap()).unwrap().score),
+                Err(index) => assert_eq!(Err(index), result),
+            }
+        }
+    }
+
+    assert_eq!(
+        Err(0),
+        records.binary_search_by_field(Entry::FIELD_SCORE, &i32::MIN)
+    );
+    assert_eq!(
+        Err(expected.len()),
+        records.binary_search_by_field(Entry::FIELD_SCORE, &i32::MAX)
+    );
+}
+
+#[test]
+fn check_sort_by_byte_array_field() {
+    let mut expected = entries();
+    let mut bytes = serialize_all(&expected);
+
+    RecordSliceMut::<Entry>::new(&mut bytes)
+        .unwrap()
+        .sort_by_field(Entry::FIELD_FLAGS);
+
+    expected.sort_by_key(|entry| entry.flags);
+
+    // Flags are unique (last byte is index), so unstable sort gives same order
+    assert_eq!(serialize_all(&expected), bytes);
+
+    let records = RecordSlice::<Entry>::new(&bytes).unwrap();
+    assert_eq!(
+        Ok(10),
+        records.binary_search_by_field(Entry::FIELD_FLAGS, &expected[10].flags)
+    );
+}
+
+#[test]
+fn check_sort_empty_and_single() {
+    let mut bytes = [];
+    let mut records = RecordSliceMut::<Pair>::new(&mut bytes).unwrap();
+    records.sort_by_field(Pair::FIELD_1);
+    assert_eq!(
+        Err(0),
+        records.as_slice().binary_search_by_field(Pair::FIELD_0, &1)
+    );
+
+    let mut bytes = Pair(1, 'a').serialize();
+    let mut records = RecordSliceMut::<Pair>::new(&mut bytes).unwrap();
+    records.sort_by_field(Pair::FIELD_1);
+    assert_eq!(
+        Ok(0),
+        records
+            .as_slice()
+            .binary_search_by_field(Pair::FIELD_1, &'a')
+    );
+    assert_eq!(Pair(1, 'a'), records.get(0).unwrap());
+}
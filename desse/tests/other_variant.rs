@@ -0,0 +1,62 @@
+use desse::{DesseSized, DesseStatic};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+enum MessageV1 {
+    Ping,
+    Data(u32),
+    #[desse(other)]
+    Unknown {
+        tag: u8,
+        payload: [u8; MessageV1::OTHER_PAYLOAD_SIZE],
+    },
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+enum MessageV2 {
+    Ping,
+    Data(u32),
+    Pair(u16, u16),
+    Flag(bool),
+}
+
+#[test]
+fn check_other_payload_size() {
+    assert_eq!(4, MessageV1::OTHER_PAYLOAD_SIZE);
+    assert_eq!(MessageV2::SIZE, MessageV1::SIZE);
+}
+
+#[test]
+fn check_known_variants() {
+    let message = MessageV1::Data(7);
+    let serialized = message.serialize();
+
+    assert_eq!(message, MessageV1::deserialize_from(&serialized).unwrap());
+    assert_eq!(
+        MessageV2::Data(7),
+        MessageV2::deserialize_from(&serialized).unwrap()
+    );
+}
+
+#[test]
+fn check_unknown_variant() {
+    let newer = MessageV2::Pair(0x0102, 0x0304).serialize();
+    let older = MessageV1::deserialize_from(&newer).unwrap();
+
+    assert_eq!(
+        MessageV1::Unknown {
+            tag: 2,
+            payload: [2, 1, 4, 3],
+        },
+        older
+    );
+    assert_eq!(newer, older.serialize());
+    assert_eq!(
+        MessageV2::Pair(0x0102, 0x0304),
+        MessageV2::deserialize_from(&older.serialize()).unwrap()
+    );
+
+    let newer = MessageV2::Flag(true).serialize();
+    let older = MessageV1::deserialize_from(&newer).unwrap();
+
+    assert_eq!(newer, older.serialize());
+}
@@ -0,0 +1,85 @@
+use desse::{DesseSized, DesseStatic, ErrorKind};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(reserved = 3)]
+struct Header {
+    kind: u8,
+    len: u16,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+enum Message {
+    #[desse(reserved = 2)]
+    Ping,
+    #[desse(reserved = 4)]
+    Data(u32),
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+struct Packet {
+    header: Header,
+    message: Message,
+}
+
+#[test]
+fn check_reserved_size() {
+    assert_eq!(1 + 2 + 3, Header::SIZE);
+    assert_eq!(1 + 4 + 4, Message::SIZE);
+    assert_eq!(Header::SIZE + Message::SIZE, Packet::SIZE);
+}
+
+#[test]
+fn check_reserved_serialize_into() {
+    let header = Header { kind: 1, len: 513 };
+    let mut bytes = [0xFF; Header::SIZE];
+
+    header.serialize_into(&mut bytes);
+    assert_eq!([1, 1, 2, 0, 0, 0], bytes);
+
+    let message = Message::Ping;
+    let mut bytes = [0xFF; Message::SIZE];
+
+    message.serialize_into(&mut bytes);
+    assert_eq!([0, 0, 0], bytes[..3]);
+}
+
+#[test]
+fn check_reserved_lenient() {
+    let packet = Packet {
+        header: Header { kind: 2, len: 4 },
+        message: Message::Data(7),
+    };
+    let mut bytes = packet.serialize();
+    bytes[Header::SIZE - 1] = 1;
+    bytes[Packet::SIZE - 1] = 1;
+
+    assert_eq!(packet, Packet::deserialize_from(&bytes).unwrap());
+}
+
+#[test]
+fn check_reserved_strict() {
+    let packet = Packet {
+        header: Header { kind: 2, len: 4 },
+        message: Message::Data(7),
+    };
+    let bytes = packet.serialize();
+    assert_eq!(packet, Packet::deserialize_from_strict(&bytes).unwrap());
+
+    let mut header_bytes = bytes;
+    header_bytes[Header::SIZE - 1] = 1;
+    assert_eq!(
+        ErrorKind::NonZeroReserved,
+        Packet::deserialize_from_strict(&header_bytes)
+            .unwrap_err()
+            .kind()
+    );
+
+    let mut message_bytes = bytes;
+    message_bytes[Packet::SIZE - 1] = 1;
+    assert_eq!(
+        ErrorKind::NonZeroReserved,
+        Packet::deserialize_from_strict(&message_bytes)
+            .unwrap_err()
+            .kind()
+    );
+}
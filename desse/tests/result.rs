@@ -0,0 +1,117 @@
+use desse::{DesseSized, DesseStatic, ErrorKind};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+struct Reply {
+    id: u32,
+    value: u64,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+enum ErrorCode {
+    NotFound,
+    Denied(u16),
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+struct Response {
+    request: u16,
+    result: Result<Reply, ErrorCode>,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+enum Message {
+    Ping,
+    Response(u8, core::result::Result<u16, u8>),
+}
+
+#[test]
+fn check_result_field() {
+    assert_eq!(2 + 1 + 12, Response::SIZE);
+
+    let ok = Response {
+        request: 1,
+        result: Ok(Reply { id: 7, value: 42 }),
+    };
+    let bytes = ok.serialize();
+
+    assert_eq!(0, bytes[2]);
+    assert_eq!(&7u32.to_le_bytes(), &bytes[3..7]);
+    assert_eq!(ok, Response::deserialize_from(&bytes).unwrap());
+    assert_eq!(ok, Response::deserialize_from_strict(&bytes).unwrap());
+
+    let err = Response {
+        request: 2,
+        result: Err(ErrorCode::Denied(3)),
+    };
+    let bytes = err.serialize();
+
+    assert_eq!(1, bytes[2]);
+    assert_eq!(&[1, 3, 0], &bytes[3..6]);
+    assert_eq!(&[0; 9], &bytes[6..]);
+    assert_eq!(err, Response::deserialize_from(&bytes).unwrap());
+    assert_eq!(err, Response::deserialize_from_strict(&bytes).unwrap());
+
+    for message in &[
+        Message::Ping,
+        Message::Response(1, Ok(300)),
+        Message::Response(2, Err(4)),
+    ] {
+        assert_eq!(
+            message,
+            &Message::deserialize_from_strict(&message.serialize()).unwrap()
+        );
+    }
+}
+
+#[test]
+fn check_result_field_padding() {
+    // Serializing over bytes of a larger `Ok` value zeroes padding after `Err` value
+    let mut bytes = Response {
+        request: 1,
+        result: Ok(Reply {
+            id: u32::MAX,
+            value: u64::MAX,
+        }),
+    }
+    .serialize();
+
+    let err = Response {
+        request: 1,
+        result: Err(ErrorCode::NotFound),
+    };
+    err.serialize_into(&mut bytes);
+
+    assert_eq!(&[0; 9], &bytes[3 + ErrorCode::SIZE..]);
+
+    bytes[Response::SIZE - 1] = 1;
+
+    assert_eq!(err, Response::deserialize_from(&bytes).unwrap());
+
+    let error = Response::deserialize_from_strict(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::NonZeroPadding, error.kind());
+    assert_eq!(Some(Response::SIZE - 1), error.offset());
+    assert_eq!("Response.result", error.path().to_string());
+}
+
+#[test]
+fn check_result_field_invalid_tag() {
+    let mut bytes = Response {
+        request: 1,
+        result: Err(ErrorCode::NotFound),
+    }
+    .serialize();
+    bytes[2] = 2;
+
+    let err = Response::deserialize_from(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::InvalidTag, err.kind());
+    assert_eq!(Some(2), err.offset());
+    assert_eq!("Response.result", err.path().to_string());
+
+    // Error of `Err` value itself
+    bytes[2] = 1;
+    bytes[3] = 5;
+
+    let err = Response::deserialize_from(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::InvalidTag, err.kind());
+    assert_eq!(Some(3), err.offset());
+}
@@ -0,0 +1,139 @@
+use core::time::Duration;
+
+use desse::{DesseSized, DesseStatic, ErrorKind};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+struct Flags {
+    enabled: bool,
+    bits: [bool; 3],
+    timeout: Duration,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+enum Message {
+    Ping,
+    Ack(u16),
+    #[desse(reserved = 1)]
+    Data {
+        id: u32,
+        urgent: bool,
+    },
+}
+
+fn flags() -> Flags {
+    Flags {
+        enabled: true,
+        bits: [false, true, false],
+        timeout: Duration::new(5, 999_999_999),
+    }
+}
+
+#[test]
+fn check_canonical_input() {
+    let bytes = flags().serialize();
+    assert_eq!(flags(), Flags::deserialize_from_strict(&bytes).unwrap());
+
+    for message in &[
+        Message::Ping,
+        Message::Ack(7),
+        Message::Data {
+            id: 9,
+            urgent: true,
+        },
+    ] {
+        let bytes = message.serialize();
+        assert_eq!(*message, Message::deserialize_from_strict(&bytes).unwrap());
+    }
+}
+
+#[test]
+fn check_reused_buffer() {
+    // Padding left by a larger variant in a reused buffer is cleared
+    let mut bytes = Message::Data {
+        id: u32::MAX,
+        urgent: true,
+    }
+    .serialize();
+
+    Message::Ack(7).serialize_into(&mut bytes);
+    assert_eq!(Message::Ack(7).serialize(), bytes);
+    assert_eq!(
+        Message::Ack(7),
+        Message::deserialize_from_strict(&bytes).unwrap()
+    );
+
+    Message::Ping.serialize_into(&mut bytes);
+    assert_eq!([0; 7], bytes);
+    assert_eq!(
+        Message::Ping,
+        Message::deserialize_from_strict(&bytes).unwrap()
+    );
+}
+
+#[test]
+fn check_invalid_bool() {
+    let mut bytes = flags().serialize();
+    bytes[0] = 2;
+
+    assert!(Flags::deserialize_from(&bytes).unwrap().enabled);
+
+    let err = Flags::deserialize_from_strict(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::InvalidBool, err.kind());
+    assert_eq!("Flags.enabled", err.path().to_string());
+
+    let mut bytes = flags().serialize();
+    bytes[3] = 0xFF;
+
+    let err = Flags::deserialize_from_strict(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::InvalidBool, err.kind());
+    assert_eq!(Some(3), err.offset());
+    assert_eq!("Flags.bits[2]", err.path().to_string());
+}
+
+#[test]
+fn check_non_zero_unused_bits() {
+    let mut bytes = flags().serialize();
+    bytes[12..16].copy_from_slice(&1_000_000_000u32.to_le_bytes());
+
+    assert_eq!(
+        Duration::new(6, 0),
+        Flags::deserialize_from(&bytes).unwrap().timeout
+    );
+
+    let err = Flags::deserialize_from_strict(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::NonZeroUnusedBits, err.kind());
+    assert_eq!(Some(12), err.offset());
+}
+
+#[test]
+fn check_non_zero_padding() {
+    // Tag (1 byte) followed by payload of largest variant (6 bytes)
+    assert_eq!(7, Message::SIZE);
+
+    let mut bytes = Message::Ack(7).serialize();
+    bytes[4] = 1;
+
+    assert_eq!(Message::Ack(7), Message::deserialize_from(&bytes).unwrap());
+
+    let err = Message::deserialize_from_strict(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::NonZeroPadding, err.kind());
+    assert_eq!(Some(4), err.offset());
+
+    let mut bytes = Message::Ping.serialize();
+    bytes[6] = 1;
+
+    let err = Message::deserialize_from_strict(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::NonZeroPadding, err.kind());
+    assert_eq!(Some(6), err.offset());
+
+    // Reserved bytes of largest variant are reported as such
+    let mut bytes = Message::Data {
+        id: 1,
+        urgent: false,
+    }
+    .serialize();
+    bytes[6] = 1;
+
+    let err = Message::deserialize_from_strict(&bytes).unwrap_err();
+    assert_eq!(ErrorKind::NonZeroReserved, err.kind());
+}
@@ -0,0 +1,64 @@
+use desse::{DesseSized, DesseStatic, ErrorKind};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(tag_type = "u16")]
+enum Wide {
+    A,
+    B(u8),
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(tag_type = "u32")]
+enum Explicit {
+    #[desse(tag = 10)]
+    Start,
+    Next,
+    #[desse(tag = 70000)]
+    Far(u16),
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+enum Small {
+    A,
+    B,
+}
+
+#[test]
+fn check_tag_type_size() {
+    assert_eq!(2 + 1, Wide::SIZE);
+    assert_eq!(4 + 2, Explicit::SIZE);
+    assert_eq!(1, Small::SIZE);
+}
+
+#[test]
+fn check_tag_type() {
+    assert_eq!([1, 0, 9], Wide::B(9).serialize());
+    assert_eq!(Wide::B(9), Wide::deserialize_from(&[1, 0, 9]).unwrap());
+    assert_eq!(Wide::A, Wide::deserialize_from(&[0, 0, 0]).unwrap());
+}
+
+#[test]
+fn check_explicit_tags() {
+    assert_eq!([10, 0, 0, 0, 0, 0], Explicit::Start.serialize());
+    assert_eq!([11, 0, 0, 0, 0, 0], Explicit::Next.serialize());
+    assert_eq!([0x70, 0x11, 0x01, 0, 5, 0], Explicit::Far(5).serialize());
+
+    for value in [Explicit::Start, Explicit::Next, Explicit::Far(5)].iter() {
+        assert_eq!(
+            value,
+            &Explicit::deserialize_from(&value.serialize()).unwrap()
+        );
+    }
+}
+
+#[test]
+fn check_invalid_tag() {
+    assert_eq!(
+        ErrorKind::InvalidTag,
+        Explicit::deserialize_from(&[0; 6]).unwrap_err().kind()
+    );
+    assert_eq!(
+        ErrorKind::InvalidTag,
+        Small::deserialize_from(&[2]).unwrap_err().kind()
+    );
+}
@@ -0,0 +1,143 @@
+use desse::{DesseSized, DesseStatic, DesseVersioned, ErrorKind};
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(version = 1)]
+struct RecordV1 {
+    id: u32,
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(version = 2, previous = "RecordV1")]
+struct RecordV2 {
+    id: u32,
+    flags: u16,
+}
+
+impl From<RecordV1> for RecordV2 {
+    fn from(old: RecordV1) -> Self {
+        Self {
+            id: old.id,
+            flags: 0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(version = 3, previous = "RecordV2")]
+struct Record {
+    id: u32,
+    flags: u16,
+    score: i64,
+}
+
+impl From<RecordV2> for Record {
+    fn from(old: RecordV2) -> Self {
+        Self {
+            id: old.id,
+            flags: old.flags,
+            score: -1,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, DesseSized, DesseStatic)]
+#[desse(version = 7)]
+enum Event {
+    Start,
+    Stop(u8),
+}
+
+#[test]
+fn check_versioned_size() {
+    assert_eq!(1 + 4, RecordV1::SIZE);
+    assert_eq!(1 + 4 + 2, RecordV2::SIZE);
+    assert_eq!(1 + 4 + 2 + 8, Record::SIZE);
+    assert_eq!(1 + 1 + 1, Event::SIZE);
+}
+
+#[test]
+fn check_versioned_struct() {
+    let record = Record {
+        id: 42,
+        flags: 0xABCD,
+        score: -7,
+    };
+    let serialized = record.serialize();
+
+    assert_eq!(3, serialized[0]);
+    assert_eq!(record, Record::deserialize_from(&serialized).unwrap());
+    assert_eq!(record, Record::deserialize_versioned(&serialized).unwrap());
+}
+
+#[test]
+fn check_versioned_enum() {
+    let event = Event::Stop(9);
+    let serialized = event.serialize();
+
+    assert_eq!([7, 1, 9], serialized);
+    assert_eq!(event, Event::deserialize_from(&serialized).unwrap());
+}
+
+#[test]
+fn check_previous_versions() {
+    let v1 = RecordV1 { id: 1 }.serialize();
+    let v2 = RecordV2 { id: 2, flags: 3 }.serialize();
+
+    assert_eq!(
+        Record {
+            id: 1,
+            flags: 0,
+            score: -1
+        },
+        Record::deserialize_versioned(&v1).unwrap()
+    );
+    assert_eq!(
+        Record {
+            id: 2,
+            flags: 3,
+            score: -1
+        },
+        Record::deserialize_versioned(&v2).unwrap()
+    );
+
+    // `deserialize_from()` needs older records to be zero-padded to current `SIZE`
+    let mut bytes = [0; Record::SIZE];
+    bytes[..RecordV2::SIZE].copy_from_slice(&v2);
+
+    assert_eq!(
+        Record {
+            id: 2,
+            flags: 3,
+            score: -1
+        },
+        Record::deserialize_from(&bytes).unwrap()
+    );
+}
+
+#[test]
+fn check_unknown_version() {
+    let mut bytes = Record {
+        id: 1,
+        flags: 2,
+        score: 3,
+    }
+    .serialize();
+    bytes[0] = 9;
+
+    assert_eq!(
+        ErrorKind::UnknownVersion,
+        Record::deserialize_from(&bytes).unwrap_err().kind()
+    );
+
+    bytes[0] = 3;
+    assert_eq!(
+        ErrorKind::InvalidSliceLength,
+        Record::deserialize_versioned(&bytes[..3])
+            .unwrap_err()
+            .kind()
+    );
+    assert_eq!(
+        ErrorKind::InvalidSliceLength,
+        Record::deserialize_versioned(&[]).unwrap_err().kind()
+    );
+}